@@ -0,0 +1,18 @@
+use std::{env, fs, path::Path};
+
+/// Compresses the offline signature database, which is embedded in the bundle and only
+/// inflated on the first lookup.
+fn main() {
+    let source = "src/parser/utils/signatures.txt";
+    println!("cargo:rerun-if-changed={}", source);
+    let signatures = fs::read_to_string(source).expect("failed to read the signature database");
+    let signatures = signatures
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .collect::<Vec<&str>>()
+        .join("\n");
+    let compressed = miniz_oxide::deflate::compress_to_vec_zlib(signatures.as_bytes(), 10);
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("signatures.z");
+    fs::write(out, compressed).expect("failed to write the signature database");
+}
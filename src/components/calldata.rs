@@ -0,0 +1,85 @@
+use crate::components::playground::clipboard::ClipboardComponent;
+
+use serde_json::Value;
+use yew::prelude::*;
+
+/// Text colors of the parameters, cycled when a function has more parameters.
+const PARAM_COLORS: [&str; 6] = [
+    "text-sky-400",
+    "text-amber-300",
+    "text-emerald-400",
+    "text-rose-400",
+    "text-violet-400",
+    "text-lime-300",
+];
+
+#[derive(Properties, PartialEq)]
+pub struct CalldataProps {
+    pub value: serde_json::Value,
+}
+
+/// Renders the output of `inspect_calldata`: one row per word with its offset, and the word
+/// and its label colored after the parameter it belongs to.
+pub struct CalldataComponent {}
+impl Component for CalldataComponent {
+    type Message = ();
+    type Properties = CalldataProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {}
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let value = &ctx.props().value;
+        let rows = value["layout"].as_array().cloned().unwrap_or_default();
+        let head_words = rows.iter().filter(|row| row["section"] == "head").count();
+
+        html! {
+            <div class="font-mono text-left">
+                if let Some(function) = value["function"].as_str() {
+                    <div class="text-gray-400 pb-1">{ function }</div>
+                }
+                if let Some(selector) = value["selector"].as_str() {
+                    <div class="flex">
+                        <span class="w-16 flex-none text-gray-600">{ "sel" }</span>
+                        <span class="pr-2 text-gray-50">{ selector }</span>
+                        <ClipboardComponent
+                            text={selector.to_string()}
+                            text_style={"text-gray-500 hover:text-gray-50"}
+                        />
+                        <span class="pl-4 text-gray-500">{ "selector" }</span>
+                    </div>
+                }
+                { for rows.iter().enumerate().map(|(i, row)| word_to_html(row, i == head_words && i > 0)) }
+                if let Some(error) = value["error"].as_str() {
+                    <div class="pt-1 text-red-400">{ format!("error: {}", error) }</div>
+                }
+            </div>
+        }
+    }
+}
+
+fn word_to_html(row: &Value, first_tail_word: bool) -> Html {
+    let color = match row["param"].as_u64() {
+        Some(param) => PARAM_COLORS[param as usize % PARAM_COLORS.len()],
+        None => "text-gray-600",
+    };
+    let word = row["word"].as_str().unwrap_or_default();
+    html! {
+        <>
+            // separates the heads of the parameters from the tail
+            if first_tail_word {
+                <div class="w-full my-1 border-t border-dashed border-gray-600" />
+            }
+            <div class="flex whitespace-nowrap">
+                <span class="w-16 flex-none text-gray-600">{ row["offset"].as_str().unwrap_or_default() }</span>
+                <span class={classes!("pr-2", color)}>{ word }</span>
+                <ClipboardComponent
+                    text={word.to_string()}
+                    text_style={"text-gray-500 hover:text-gray-50"}
+                />
+                <span class={classes!("pl-4", "opacity-75", color)}>{ row["label"].as_str().unwrap_or_default() }</span>
+            </div>
+        </>
+    }
+}
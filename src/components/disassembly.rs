@@ -0,0 +1,113 @@
+use crate::components::playground::clipboard::ClipboardComponent;
+
+use serde_json::Value;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct DisassemblyProps {
+    pub value: serde_json::Value,
+}
+
+/// Renders the output of `disassemble`: the dispatcher selectors, then the instructions of
+/// each section with their program counter, split into basic blocks.
+pub struct DisassemblyComponent {}
+impl Component for DisassemblyComponent {
+    type Message = ();
+    type Properties = DisassemblyProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {}
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let value = &ctx.props().value;
+        let selectors = value["selectors"].as_array().cloned().unwrap_or_default();
+        let sections = value["sections"].as_array().cloned().unwrap_or_default();
+
+        html! {
+            <div class="font-mono text-left">
+                { for selectors.iter().map(selector_to_html) }
+                { for sections.iter().map(section_to_html) }
+                if let Some(metadata) = value["metadata"].as_object() {
+                    <div class="pt-1 flex whitespace-nowrap">
+                        <span class="w-16 flex-none text-gray-600">{ metadata["offset"].as_str().unwrap_or_default() }</span>
+                        <span class="pr-2 text-gray-500">{ format!("metadata ({} bytes)", metadata["length"]) }</span>
+                        <ClipboardComponent
+                            text={metadata["data"].as_str().unwrap_or_default().to_string()}
+                            text_style={"text-gray-500 hover:text-gray-50"}
+                        />
+                    </div>
+                }
+                if let Some(args) = value["args"].as_str() {
+                    <div class="flex whitespace-nowrap">
+                        <span class="w-16 flex-none text-gray-600">{ "args" }</span>
+                        <span class="pr-2 text-gray-500 truncate">{ args }</span>
+                        <ClipboardComponent
+                            text={args.to_string()}
+                            text_style={"text-gray-500 hover:text-gray-50"}
+                        />
+                    </div>
+                }
+            </div>
+        }
+    }
+}
+
+fn selector_to_html(row: &Value) -> Html {
+    let function = match (row["function"].as_str(), row["candidates"][0].as_str()) {
+        (Some(function), _) => function.to_string(),
+        // unresolved by the notebook, best guess from the signature database
+        (None, Some(candidate)) => format!("{}?", candidate),
+        (None, None) => String::new(),
+    };
+    html! {
+        <div class="flex whitespace-nowrap">
+            <span class="w-16 flex-none text-gray-600">{ "sel" }</span>
+            <span class="pr-2 text-sky-400">{ row["selector"].as_str().unwrap_or_default() }</span>
+            <span class="pr-2 text-gray-50">{ function }</span>
+            <span class="text-gray-500">{ format!("→ {}", row["jumpdest"].as_str().unwrap_or_default()) }</span>
+        </div>
+    }
+}
+
+fn section_to_html(section: &Value) -> Html {
+    let instructions = section["instructions"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    html! {
+        <>
+            <div class="pt-1 pb-1 text-gray-400">
+                { format!("{} @ {} ({} bytes)", section["name"].as_str().unwrap_or_default(), section["offset"].as_str().unwrap_or_default(), section["length"]) }
+            </div>
+            { for instructions.iter().map(instruction_to_html) }
+        </>
+    }
+}
+
+fn instruction_to_html(row: &Value) -> Html {
+    let op = row["op"].as_str().unwrap_or_default();
+    let color = match op {
+        "JUMPDEST" => "text-emerald-400",
+        "JUMP" | "JUMPI" => "text-amber-300",
+        "STOP" | "RETURN" | "REVERT" | "INVALID" | "SELFDESTRUCT" => "text-rose-400",
+        op if op.starts_with("PUSH") => "text-sky-400",
+        _ => "text-gray-50",
+    };
+    html! {
+        <>
+            // separates the basic blocks
+            if row["block"].as_bool().unwrap_or_default() {
+                <div class="w-full my-1 border-t border-dashed border-gray-600" />
+            }
+            <div class="flex whitespace-nowrap">
+                <span class="w-16 flex-none text-gray-600">{ row["pc"].as_str().unwrap_or_default() }</span>
+                <span class={classes!("pr-2", color)}>{ op }</span>
+                <span class="text-gray-400">{ row["push"].as_str().unwrap_or_default() }</span>
+                if row["truncated"].as_bool().unwrap_or_default() {
+                    <span class="pl-2 text-rose-400">{ "(truncated)" }</span>
+                }
+            </div>
+        </>
+    }
+}
@@ -42,10 +42,9 @@ fn json_to_html(value: &Value, indent: usize) -> Html {
                     html! { <div>{format!("{}{}", indent_str, "[],")}</div> }
                 } else {
                     html! { <> {
-                        for vec.iter().map(|item| html! {
-                            <>
-                                {json_to_html(item, indent + 1)}
-                            </>
+                        for vec.iter().map(|item| match item {
+                            Value::Object(obj) if obj.len() > 1 => array_item_to_html(item, indent + 1),
+                            _ => json_to_html(item, indent + 1),
                         })
                     } </> }
                 }
@@ -64,38 +63,82 @@ fn single_obj_to_html(obj: &Map<String, Value>, indent: usize) -> Html {
     let indent_str = "\u{00a0}".repeat(indent * 2);
     if obj.is_empty() {
         html! { <div>{"{}"}</div> }
-    } else if obj.len() == 1 {
-        let (k, v) = obj.iter().next().unwrap();
-        let v = if k == "address" {
-            let u = trim_quotes(&v.to_string())
-                .parse::<U256>()
-                .unwrap_or_default();
-            format!("\"{}\"", u256_to_address(u).to_string())
-        } else {
-            v.to_string()
-        };
-        html! {<>
-            <div class="flex">
-                <span class="pr-2">{format!("{}{}: {}", indent_str, k, v)}</span>
-                    <ClipboardComponent
-                        text={trim_quotes(&v.to_string())}
-                        text_style={"text-gray-500 hover:text-gray-50"}
-                    />
-            </div> if k == "fn_selector" { <br /> }
-        </>}
     } else {
         html! { <>
-        { for obj.iter().map(|(k, v)| html! {
-            <div>
-                <span>{format!("{}{}: {}", indent_str, k, "\u{007b}")}</span>
-                {json_to_html(v, indent + 1)}
-                <span>{format!("{}\u{00a7}", indent_str)}</span>
-            </div>
+        { for obj.iter().map(|(k, v)| match v {
+            Value::Object(o) if !o.is_empty() => html! {
+                <div>
+                    <div>{format!("{}{}: {}", indent_str, k, "\u{007b}")}</div>
+                    {single_obj_to_html(o, indent + 1)}
+                    <div>{format!("{}{}", indent_str, "\u{007d}")}</div>
+                </div>
+            },
+            Value::Array(vec) if !vec.is_empty() => html! {
+                <div>
+                    <div>{format!("{}{}: [", indent_str, k)}</div>
+                    { for vec.iter().map(|item| array_item_to_html(item, indent + 1)) }
+                    <div>{format!("{}]", indent_str)}</div>
+                </div>
+            },
+            _ => key_value_to_html(k, v, indent),
         })}
         </> }
     }
 }
 
+fn key_value_to_html(k: &str, v: &Value, indent: usize) -> Html {
+    let indent_str = "\u{00a0}".repeat(indent * 2);
+    let v = if k == "address" {
+        let u = trim_quotes(&v.to_string())
+            .parse::<U256>()
+            .unwrap_or_default();
+        format!("\"{}\"", u256_to_address(u))
+    } else {
+        v.to_string()
+    };
+    html! {<>
+        <div class="flex">
+            <span class="pr-2">{format!("{}{}: {}", indent_str, k, v)}</span>
+                <ClipboardComponent
+                    text={trim_quotes(&v.to_string())}
+                    text_style={"text-gray-500 hover:text-gray-50"}
+                />
+        </div> if k == "fn_selector" { <br /> }
+    </>}
+}
+
+fn array_item_to_html(item: &Value, indent: usize) -> Html {
+    let indent_str = "\u{00a0}".repeat(indent * 2);
+    match item {
+        Value::Object(o) if o.len() == 1 && !o.values().any(|v| v.is_object() || v.is_array()) => {
+            single_obj_to_html(o, indent)
+        }
+        Value::Object(o) => html! {
+            <div>
+                <div>{format!("{}{}", indent_str, "\u{007b}")}</div>
+                {single_obj_to_html(o, indent + 1)}
+                <div>{format!("{}{},", indent_str, "\u{007d}")}</div>
+            </div>
+        },
+        Value::Array(vec) => html! {
+            <div>
+                <div>{format!("{}[", indent_str)}</div>
+                { for vec.iter().map(|item| array_item_to_html(item, indent + 1)) }
+                <div>{format!("{}],", indent_str)}</div>
+            </div>
+        },
+        _ => html! {
+            <div class="flex">
+                <span class="pr-2">{format!("{}{},", indent_str, item)}</span>
+                <ClipboardComponent
+                    text={trim_quotes(&item.to_string())}
+                    text_style={"text-gray-500 hover:text-gray-50"}
+                />
+            </div>
+        },
+    }
+}
+
 fn array_obj_to_html(obj: Option<&Map<String, Value>>, indent: usize) -> Html {
     let indent_str = "\u{00a0}".repeat(indent * 2);
     match obj {
@@ -122,15 +165,7 @@ fn array_obj_to_html(obj: Option<&Map<String, Value>>, indent: usize) -> Html {
                 }})}
                 </> }
             } else {
-                html! { <>
-                { for obj.iter().map(|(_, v)| html! {
-                    <div>
-                        <span>{format!("{}{},", indent_str, "\u{007b}")}</span>
-                        {json_to_html(v, indent + 1)}
-                        <span>{format!("{}\u{00a7}", indent_str)}</span>
-                    </div>
-                })}
-                </> }
+                array_item_to_html(&Value::Object(obj.clone()), indent)
             }
         }
         None => html! { <div>{"{},"}</div> },
@@ -143,7 +178,8 @@ fn array_to_html(vec: &Vec<Value>, indent: usize) -> Html {
         html! { <div>{"[]"}</div> }
     } else {
         match &vec[0] {
-            Value::Object(obj) => {
+            // typed arrays, ie: `uint256[]: [` followed by the values
+            Value::Object(obj) if obj.len() <= 1 => {
                 if !obj.is_empty() {
                     let obj_type = obj.iter().next().unwrap().0;
                     html! {
@@ -165,12 +201,11 @@ fn array_to_html(vec: &Vec<Value>, indent: usize) -> Html {
                 html! {
                 <>
                     <div>{format!("{}[", indent_str)}</div> <> {
-                    for vec.iter().map(|item| html! {
-                        <>
-                            {json_to_html(item, indent + 1)}
-                        </>
+                    for vec.iter().map(|item| match item {
+                        Value::Object(_) => array_item_to_html(item, indent + 1),
+                        _ => json_to_html(item, indent + 1),
                     })
-                     } </> <div>{"{}],"}</div>
+                     } </> <div>{format!("{}],", indent_str)}</div>
                 </>
                     }
             }
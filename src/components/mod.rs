@@ -1,3 +1,5 @@
+pub mod calldata;
+pub mod disassembly;
 pub mod json;
 pub mod playground;
 pub mod search;
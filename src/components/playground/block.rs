@@ -4,7 +4,12 @@ use super::{
 };
 use crate::parser::types::result::ParseResult;
 use crate::parser::{self, utils};
-use crate::{components::json::JsonComponent, parser::utils::count_chars};
+use crate::{
+    components::{
+        calldata::CalldataComponent, disassembly::DisassemblyComponent, json::JsonComponent,
+    },
+    parser::utils::count_chars,
+};
 
 use gloo_console::log;
 use web_sys::HtmlTextAreaElement;
@@ -38,20 +43,36 @@ pub struct BlockComponent {
     initialized: bool,
     input: BlockInput,
     output: ParseResult,
+    // canonical signatures hashed by `selector`, shown next to the result
+    signatures: Vec<String>,
 }
 
 impl BlockComponent {
     fn parse_input(&mut self, blocks: &Vec<BlockState>) {
-        let s = self.input.get_value().replace("\n", "");
-        self.output = parser::parse(&s, blocks);
+        self.output = parser::parse(self.input.get_value(), blocks);
+        self.signatures = parser::hashed_signatures(self.input.get_value(), blocks);
     }
 
     fn is_json(&self) -> bool {
         self.output.is_json()
     }
 
+    /// Output of `inspect_calldata`, rendered as annotated words rather than as json.
+    fn is_calldata_layout(&self) -> bool {
+        self.output
+            .get_json()
+            .is_some_and(|json| json["layout"].is_array())
+    }
+
+    /// Output of `disassemble`, rendered as a listing rather than as json.
+    fn is_disassembly(&self) -> bool {
+        self.output
+            .get_json()
+            .is_some_and(|json| json["sections"].is_array())
+    }
+
     fn is_str(&self) -> bool {
-        self.output.is_str()
+        self.output.is_str() || self.output.is_address() || self.output.is_array()
     }
 }
 
@@ -71,12 +92,14 @@ impl Component for BlockComponent {
                     .get(ctx.props().block_index)
                     .unwrap()
                     .get_result(),
+                signatures: parser::hashed_signatures(input.get_value(), &ctx.props().blocks),
             },
             None => Self {
                 min_height: 110,
                 initialized: false,
                 input: BlockInput::default(),
                 output: ParseResult::NAN,
+                signatures: Vec::new(),
             },
         }
     }
@@ -174,7 +197,19 @@ impl Component for BlockComponent {
                         }>
                     </textarea>
                 </div>
-                if self.is_json() {
+                if self.is_calldata_layout() {
+                <div class="col-span-2 overflow-x-auto peer-focus-within/input:text-emerald-400">
+                    <p class="pt-0 text-right text-gray-400">{ "calldata:" }</p>
+                    <CalldataComponent value={self.output.get_json().unwrap()}/>
+                </div>
+                }
+                else if self.is_disassembly() {
+                <div class="col-span-2 overflow-x-auto peer-focus-within/input:text-emerald-400">
+                    <p class="pt-0 text-right text-gray-400">{ "bytecode:" }</p>
+                    <DisassemblyComponent value={self.output.get_json().unwrap()}/>
+                </div>
+                }
+                else if self.is_json() {
                 <div class="col-span-2 overflow-x-auto text-right peer-focus-within/input:text-emerald-400">
                     <p class="pt-0 text-gray-400">{ "json:" }</p>
                     <div class="w-full text-left"><JsonComponent
@@ -193,6 +228,9 @@ impl Component for BlockComponent {
                                 <div class="w-full">{ v }</div>
                             } })
                         }
+                        { for self.signatures.iter().map(|sig| html! {
+                            <div class="w-full text-gray-500">{ format!("keccak256(\"{}\")", sig) }</div>
+                        }) }
                     </div>
                 </div>
                 } else if ctx.props().toggle {
@@ -257,6 +295,8 @@ impl Component for BlockComponent {
                 .get(ctx.props().block_index)
                 .unwrap()
                 .get_result();
+            self.signatures =
+                parser::hashed_signatures(self.input.get_value(), &ctx.props().blocks);
         }
         true
     }
@@ -4,7 +4,9 @@ use super::{
     types::{BlockInput, BlockState, Notebook, NotebookBlock},
 };
 use crate::{
-    components::playground::types::{download_notebook, load_notebook},
+    components::playground::types::{
+        download_notebook, load_artifact, load_notebook, place_artifact,
+    },
     parser::types::result::ParseResult,
 };
 
@@ -19,7 +21,9 @@ pub enum Msg {
     Import,
     ImportFinished,
     LoadFile(File),
+    LoadArtifact(File),
     NotebookLoaded((Vec<BlockState>, Vec<BlockInput>)),
+    ArtifactLoaded((Vec<BlockState>, Vec<BlockInput>)),
     // block state
     AddBlock,
     FocusBlock,
@@ -34,6 +38,8 @@ pub struct FrameComponent {
     export: bool,
     blocks: Vec<BlockState>,
     inputs: Option<Vec<BlockInput>>,
+    // index of the first imported block, as artifacts are placed within the notebook
+    import_from: usize,
     focus: usize,
     focus_on_render: bool,
     label_change: bool,
@@ -64,13 +70,16 @@ impl FrameComponent {
     fn should_import_input(&self, index: usize) -> Option<BlockInput> {
         let inputs = self.inputs.clone();
         match inputs {
-            Some(inputs) => inputs.get(index).cloned(),
+            Some(inputs) => inputs.get(index.checked_sub(self.import_from)?).cloned(),
             None => None,
         }
     }
 
     fn should_import_state(&self, index: usize) -> Option<BlockState> {
-        if self.inputs.is_some() {
+        let imported = self.inputs.as_ref().is_some_and(|inputs| {
+            (self.import_from..self.import_from + inputs.len()).contains(&index)
+        });
+        if imported {
             self.blocks.get(index).cloned()
         } else {
             None
@@ -96,6 +105,7 @@ impl Component for FrameComponent {
             export: false,
             blocks: vec![BlockState::from_id(0)],
             inputs: None,
+            import_from: 0,
             focus: 0,
             focus_on_render: true,
             label_change: false,
@@ -147,9 +157,20 @@ impl Component for FrameComponent {
                 return false;
             }
             Msg::LoadFile(file) => {
+                // files that aren't notebooks are tried as contract artifacts
+                let artifact = file.clone();
                 load_notebook(
                     file,
                     ctx.link().callback(Msg::NotebookLoaded),
+                    ctx.link()
+                        .callback(move |_| Msg::LoadArtifact(artifact.clone())),
+                );
+                return false;
+            }
+            Msg::LoadArtifact(file) => {
+                load_artifact(
+                    file,
+                    ctx.link().callback(Msg::ArtifactLoaded),
                     ctx.link().callback(|_| Msg::ImportFinished),
                 );
                 return false;
@@ -157,6 +178,13 @@ impl Component for FrameComponent {
             Msg::NotebookLoaded((states, inputs)) => {
                 self.blocks = states;
                 self.inputs = Some(inputs);
+                self.import_from = 0;
+            }
+            Msg::ArtifactLoaded((states, inputs)) => {
+                for state in states {
+                    self.import_from = place_artifact(&mut self.blocks, state);
+                }
+                self.inputs = Some(inputs);
             }
             Msg::ImportFinished => {
                 self.inputs = None;
@@ -281,6 +309,10 @@ impl Component for FrameComponent {
     }
 
     fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        // the first block ends the import of a notebook, but not of artifacts placed after it
+        if self.inputs.is_some() && self.import_from > 0 {
+            ctx.link().send_message(Msg::ImportFinished);
+        }
         if !first_render && self.focus_on_render && !ctx.props().search_mode {
             if let Some(textarea) = ctx.props().focus_ref.cast::<HtmlTextAreaElement>() {
                 let _ = textarea.focus();
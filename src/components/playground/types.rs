@@ -1,4 +1,4 @@
-use crate::parser::types::result::ParseResult;
+use crate::parser::{self, types::result::ParseResult, utils::artifact};
 
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::closure::Closure;
@@ -78,6 +78,39 @@ impl NotebookBlock {
             result: state.result.clone(),
         }
     }
+
+    /// Block holding a Foundry or Hardhat artifact, labeled after its contract. The input is the
+    /// parsed artifact itself, so the block evaluates to the same object when re-parsed.
+    pub fn from_artifact(json: &str, file_name: &str) -> Result<Self, String> {
+        let artifact = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let contract = artifact::parse_artifact(&artifact, file_name)?;
+        Ok(Self {
+            label: contract["contract"]
+                .as_str()
+                .unwrap_or_default()
+                .to_lowercase()
+                .replace(' ', "_"),
+            input: contract.to_string(),
+            height: 110,
+            result: ParseResult::Json(contract),
+        })
+    }
+}
+
+/// Places the block of an imported artifact in the notebook, returning its index. A block with
+/// the same label (ie: the artifact imported again after a rebuild) is replaced, so references
+/// to the contract resolve to the new artifact; other blocks are appended.
+pub fn place_artifact(blocks: &mut Vec<BlockState>, state: BlockState) -> usize {
+    match blocks.iter().position(|block| block.id == state.id) {
+        Some(index) => {
+            blocks[index] = state;
+            index
+        }
+        None => {
+            blocks.push(state);
+            blocks.len() - 1
+        }
+    }
 }
 
 // Alias for Vec<NotebookBlock>
@@ -88,6 +121,7 @@ trait NotebookMethods {
     fn to_json(&self) -> String;
     fn from_json(json: &str) -> Self;
     fn into_blocks(self) -> (Vec<BlockState>, Vec<BlockInput>);
+    fn redact_keys(&mut self);
 }
 
 impl NotebookMethods for Notebook {
@@ -116,9 +150,37 @@ impl NotebookMethods for Notebook {
 
         (block_states, block_inputs)
     }
+
+    /// Clears the input and result of every block holding private key material, so keys are
+    /// never written to disk. Blocks referenced by a key argument are redacted too, including
+    /// the blocks those keys are derived from.
+    fn redact_keys(&mut self) {
+        let mut redact = vec![false; self.len()];
+        let mut labels = Vec::new();
+        for (i, block) in self.iter().enumerate() {
+            let (literal, refs) = parser::key_args(&block.input);
+            redact[i] = literal;
+            labels.extend(refs);
+        }
+        while let Some(label) = labels.pop() {
+            for (i, block) in self.iter().enumerate() {
+                if !redact[i] && block.label.to_lowercase() == label {
+                    redact[i] = true;
+                    labels.extend(parser::referenced_labels(&block.input));
+                }
+            }
+        }
+        for (block, redact) in self.iter_mut().zip(redact) {
+            if redact {
+                block.input = String::new();
+                block.result = ParseResult::NAN;
+            }
+        }
+    }
 }
 
-pub fn download_notebook(notebook: Notebook) {
+pub fn download_notebook(mut notebook: Notebook) {
+    notebook.redact_keys();
     let json = notebook.to_json();
     let array = js_sys::Array::new();
     array.push(&JsValue::from_str(&json));
@@ -156,8 +218,36 @@ pub fn load_notebook(
                 let (states, inputs) = notebook.into_blocks();
                 success_callback.emit((states, inputs));
             }
-            Err(_) => {
-                gloo_console::log!("Error parsing import file!");
+            Err(_) => error_callback.emit(()),
+        };
+    }) as Box<dyn FnMut()>);
+
+    file_reader
+        .borrow_mut()
+        .set_onloadend(Some(onloadend.as_ref().unchecked_ref()));
+    file_reader.borrow().read_as_text(&file).unwrap();
+    onloadend.forget();
+}
+
+/// Loads a Foundry or Hardhat artifact as a single block, to be appended to the notebook.
+pub fn load_artifact(
+    file: File,
+    success_callback: Callback<(Vec<BlockState>, Vec<BlockInput>)>,
+    error_callback: Callback<()>,
+) {
+    let file_reader = std::rc::Rc::new(std::cell::RefCell::new(FileReader::new().unwrap()));
+    let reader_clone = file_reader.clone();
+    let file_name = file.name();
+
+    let onloadend = Closure::wrap(Box::new(move || {
+        let result = reader_clone.borrow().result().unwrap().as_string().unwrap();
+        match NotebookBlock::from_artifact(&result, &file_name) {
+            Ok(block) => {
+                let (states, inputs) = vec![block].into_blocks();
+                success_callback.emit((states, inputs));
+            }
+            Err(e) => {
+                gloo_console::log!(format!("Error parsing import file! {}", e));
                 error_callback.emit(());
             }
         };
@@ -169,3 +259,83 @@ pub fn load_notebook(
     file_reader.borrow().read_as_text(&file).unwrap();
     onloadend.forget();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const KEY: &str = "0x4646464646464646464646464646464646464646464646464646464646464646";
+    const HASH: &str = "0xdaf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53";
+
+    fn block(label: &str, input: &str) -> NotebookBlock {
+        NotebookBlock {
+            label: label.to_string(),
+            input: input.to_string(),
+            height: 0,
+            result: ParseResult::NAN,
+        }
+    }
+
+    #[test]
+    fn redact_keys_covers_every_key_function() {
+        let mut notebook = vec![
+            block("signed", &format!("sign({}, {})", HASH, KEY)),
+            block("pubkey", &format!("pubkey({})", KEY)),
+            block("public_key", &format!("public_key({})", KEY)),
+            block("address_of", &format!("address_of({})", KEY)),
+            block("tx", &format!("tx(chain_id=1, key={})", KEY)),
+            block(
+                "tx_private_key",
+                &format!("tx(chain_id=1, private_key={})", KEY),
+            ),
+            block("message", "keccak256(\"message\")"),
+        ];
+        notebook.redact_keys();
+        for block in &notebook[..6] {
+            assert!(block.input.is_empty(), "{} was not redacted", block.label);
+        }
+        assert_eq!(notebook[6].input, "keccak256(\"message\")");
+    }
+
+    #[test]
+    fn redact_keys_follows_references() {
+        let mut notebook = vec![
+            block("seed", "keccak256(\"seed\")"),
+            block("my_key", "seed + 1"),
+            block("signer", "public_key(my_key)"),
+        ];
+        notebook.redact_keys();
+        assert!(notebook[0].input.is_empty());
+        assert!(notebook[1].input.is_empty());
+        // the public key itself is kept
+        assert_eq!(notebook[2].input, "public_key(my_key)");
+    }
+
+    #[test]
+    fn place_artifact_replaces_the_block_with_its_label() {
+        let artifact = |bytecode: &str| {
+            let json = json!({
+                "contractName": "Counter",
+                "abi": [],
+                "bytecode": bytecode,
+                "deployedBytecode": bytecode,
+            });
+            let block = NotebookBlock::from_artifact(&json.to_string(), "Counter.json").unwrap();
+            BlockState {
+                id: block.label,
+                result: block.result,
+            }
+        };
+        let mut blocks = vec![BlockState::from_id(0)];
+        assert_eq!(place_artifact(&mut blocks, artifact("0x00")), 1);
+        blocks.push(BlockState::from_id(2));
+        assert_eq!(place_artifact(&mut blocks, artifact("0x01")), 1);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[1].get_id(), "counter");
+        assert_eq!(
+            blocks[1].get_result().get_json().unwrap()["bytecode"],
+            "0x01"
+        );
+    }
+}
@@ -133,7 +133,14 @@ impl Component for DetailCardComponent {
 
 fn format_text_with_code(text: &str, code_style: &'static str) -> Html {
     html! {
-        for text.split('\n').map(|line| parse_line(line, code_style))
+        for text.split('\n').map(|line| {
+            // ie: the EIP links of the opcode reference
+            if line.starts_with("https://") {
+                html! { <p><a href={line.to_string()} target="_blank" class="underline text-sky-300/80">{line}</a></p> }
+            } else {
+                parse_line(line, code_style)
+            }
+        })
     }
 }
 
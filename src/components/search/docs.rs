@@ -16,6 +16,8 @@ pub enum CommandType {
     Constant,
     Operation,
     Conversion,
+    Opcode,
+    Precompile,
 }
 
 impl CommandType {
@@ -26,11 +28,13 @@ impl CommandType {
             CommandType::Constant => "Constant",
             CommandType::Operation => "Operation",
             CommandType::Conversion => "Conversion",
+            CommandType::Opcode => "Opcode",
+            CommandType::Precompile => "Precompile",
         }
     }
 }
 
-pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
+pub static SEARCH_ITEMS: &[SearchItemData; 105] = &[
     // START: INPUT COMMANDS
     SearchItemData {
         id: 0,
@@ -59,9 +63,27 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         example: Some("\"Hello, World!\"   // double quotes ('Hello, World!')\n'Foo Bar'         // single quotes ('Foo Bar')"),
         desc: "Strings must be wrapped around quotation marks. Both single and double quotes are accepted.",
     },
-    // END: INPUT COMMANDS
     SearchItemData {
         id: 3,
+        command: "interface",
+        c_type: CommandType::Input,
+        alias: None,
+        params: None,
+        example: Some("interface IERC20 {\n  event Transfer(address indexed from, address indexed to, uint256 value);\n  function transfer(address to, uint256 amount) external returns (bool);\n}\n \nIERC20.transfer(0xd8da6bf2..7aa96045, 1)  // calldata (0xa9059cbb..)\nselector(IERC20.transfer)                // 0xa9059cbb\ndecode_log(IERC20.Transfer, topics, data)\ndecode(IERC20, \"0xa9059cbb..\")"),
+        desc: "Pasted Solidity interfaces, or human-readable ABI items one per line (`function ..`, `event ..`, `error ..`), evaluate to a JSON ABI. Structs, enums and user-defined value types are resolved to their ABI types, and contract types to `address`.\nInterfaces can be referenced by their name: `IERC20` is the ABI (ie: for `decode` or `decode_revert`), `IERC20.Transfer` the signature of an item and `IERC20.transfer(..)` the calldata of a call.",
+    },
+    SearchItemData {
+        id: 4,
+        command: "artifact",
+        c_type: CommandType::Input,
+        alias: None,
+        params: None,
+        example: Some("Counter.increment()        // calldata (0xd09de08a)\nselector(Counter.setNumber) // 0x3fb5c1cb\nCounter.deployedBytecode   // 0x6080..\nCounter.methodIdentifiers  // { \"increment()\": \"d09de08a\", .. }\nCounter.storageLayout      // { storage: [{ label: \"number\", slot: \"0\", .. }], .. }"),
+        desc: "Foundry (`out/Counter.sol/Counter.json`) and Hardhat artifacts can be imported with ⌘I. They're added to the notebook as a block named after the contract, holding its `abi`, `bytecode`, `deployedBytecode`, `methodIdentifiers` and `storageLayout` (when compiled with it). Importing the artifact again replaces that block.\nThe contract is then referenced like a pasted interface, and its fields by name: `Counter.deployedBytecode`.",
+    },
+    // END: INPUT COMMANDS
+    SearchItemData {
+        id: 5,
         command: "unchecked",
         c_type: CommandType::Function,
         alias: None,
@@ -71,7 +93,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
     },
     // START: OPERATION COMMANDS
     SearchItemData {
-        id: 4,
+        id: 6,
         command: "+",
         c_type: CommandType::Operation,
         alias: None,
@@ -80,7 +102,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the non-overflowing addition of two values.",
     },
     SearchItemData {
-        id: 5,
+        id: 7,
         command: "-",
         c_type: CommandType::Operation,
         alias: None,
@@ -89,7 +111,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the non-underflowing subtraction of two values.",
     },
     SearchItemData {
-        id: 6,
+        id: 8,
         command: "*",
         c_type: CommandType::Operation,
         alias: None,
@@ -98,7 +120,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the non-overflowing multiplication of two values.",
     },
     SearchItemData {
-        id: 7,
+        id: 9,
         command: "/",
         c_type: CommandType::Operation,
         alias: None,
@@ -107,7 +129,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the division of two values. The result is rounded down to the nearest integer.",
     },
     SearchItemData {
-        id: 8,
+        id: 10,
         command: "%",
         c_type: CommandType::Operation,
         alias: None,
@@ -116,7 +138,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the modulus of two values.",
     },
     SearchItemData {
-        id: 9,
+        id: 11,
         command: "**",
         c_type: CommandType::Operation,
         alias: None,
@@ -125,7 +147,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the power of two values.",
     },
     SearchItemData {
-        id: 10,
+        id: 12,
         command: ">>",
         c_type: CommandType::Operation,
         alias: None,
@@ -134,7 +156,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Right shift a number by n bits",
     },
     SearchItemData {
-        id: 11,
+        id: 13,
         command: "<<",
         c_type: CommandType::Operation,
         alias: None,
@@ -145,7 +167,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
     // END: OPERATION COMMANDS
     // START: CONVERSION COMMANDS
     SearchItemData {
-        id: 12,
+        id: 14,
         command: "EVM gas units",
         c_type: CommandType::Conversion,
         alias: Some("wei, kwei, mwei, gwei, szabo, finney, ether"),
@@ -154,7 +176,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Converts the input value to the equivalent amount. Follows the pattern: 'unit_from' to 'unit_to'.\nWhen 'unit_to' is bigger than 'unit_from', the result is rounded down.",
     },
     SearchItemData {
-        id: 13,
+        id: 15,
         command: "Time units",
         c_type: CommandType::Conversion,
         alias: Some("seconds, minutes, hours, days, weeks, months, years"),
@@ -165,7 +187,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
     // END: CONVERSION COMMANDS
     // START: CONSTANTS
     SearchItemData {
-        id: 14,
+        id: 16,
         command: "max_uint",
         c_type: CommandType::Constant,
         alias: Some("max_u256, type(uint256).max"),
@@ -174,7 +196,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Evaluates to the max uint possible with 32 bytes or 256 bits",
     },
     SearchItemData {
-        id: 15,
+        id: 17,
         command: "zero_address",
         c_type: CommandType::Constant,
         alias: Some("address(0), addr(0), address_zero, zadd"),
@@ -183,7 +205,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Evaluates to the zero address",
     },
     SearchItemData {
-        id: 16,
+        id: 18,
         command: "now",
         c_type: CommandType::Constant,
         alias: None,
@@ -194,7 +216,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
     // END: CONSTANTS COMMANDS
     // START: FUNCTION COMMANDS
     SearchItemData {
-        id: 17,
+        id: 19,
         command: "sqrt",
         c_type: CommandType::Function,
         alias: None,
@@ -203,7 +225,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the square root of the input value. The result is rounded down to the nearest integer.",
     },
     SearchItemData {
-        id: 18,
+        id: 20,
         command: "root",
         c_type: CommandType::Function,
         alias: None,
@@ -212,34 +234,34 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the Nth root of the input value. The result is rounded down to the nearest integer.",
     },
     SearchItemData {
-        id: 19,
+        id: 21,
         command: "checksum",
         c_type: CommandType::Function,
         alias: Some("addr, address"),
-        params: Some("checksum(addr: `bytes20`)"),
-        example: Some("// address checksum (0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045)\nchecksum(0xd8da6bf26964af9d7eed9e03e53415d37aa96045)"),
-        desc: "Calculates the checksum of an Ethereum address",
+        params: Some("checksum(addr: `bytes20`, chain_id?: `uint64`)"),
+        example: Some("// address checksum (0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045)\nchecksum(0xd8da6bf26964af9d7eed9e03e53415d37aa96045)\n \n// chain-specific checksum (0xD8Da6bf26964Af9d7EEd9e03e53415d37AA96045)\nchecksum(0xd8da6bf26964af9d7eed9e03e53415d37aa96045, 1)"),
+        desc: "Calculates the EIP-55 checksum of an Ethereum address.\nWhen a `chain_id` is provided, it calculates the chain-specific EIP-1191 checksum instead.\nMixed-case address literals are validated, so an address with an invalid checksum evaluates to `-`.",
     },
     SearchItemData {
-        id: 20,
+        id: 22,
         command: "selector",
         c_type: CommandType::Function,
         alias: None,
         params: Some("selector(fn_sig: `str`)"),
-        example: Some("// 4-byte function selector (0xa9059cbb)\nselector(\"transfer(address,uint256)\")"),
-        desc: "Returns the 4-byte function selector for Ethereum function signatures.\n The function signature must only the function name followed by the parameter types in parentheses (without parameter names).",
+        example: Some("// 4-byte function selector (0xa9059cbb)\nselector(\"transfer(address,uint256)\")\n \n// hashes transfer(address,uint256) as well\nselector(\"transfer(address to, uint amount)\")"),
+        desc: "Returns the 4-byte function selector for Ethereum function signatures.\nThe signature is canonicalized before being hashed (see `canonical_signature`), and its canonical form is shown next to the selector.",
     },
     SearchItemData {
-        id: 21,
+        id: 23,
         command: "keccak256",
         c_type: CommandType::Function,
         alias: Some("hash, sha3"),
         params: Some("keccak256(input: `str`)"),
         example: Some("// keccak hash (0x47173285a8d7..fa254cb01fad)\nkeccak256(\"hello world\")"),
-        desc: "Computes the KECCAK-256 hash of the input following the SHA-3 standard.",
+        desc: "Computes the KECCAK-256 hash of the input following the SHA-3 standard. Hex strings (ie: the output of `abi_encode_packed`) are hashed as bytes.",
     },
     SearchItemData {
-        id: 22,
+        id: 24,
         command: "base64_encode",
         c_type: CommandType::Function,
         alias: Some("b64_encode, b64encode"),
@@ -248,7 +270,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Encodes the input string into Base64 format",
     },
     SearchItemData {
-        id: 23,
+        id: 25,
         command: "base64_decode",
         c_type: CommandType::Function,
         alias: Some("b64_decode, b64decode"),
@@ -257,34 +279,34 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Decodes the Base64 encoded string back into plain text",
     },
     SearchItemData {
-        id: 24,
+        id: 26,
         command: "abi_encode",
         c_type: CommandType::Function,
         alias: None,
         params: Some("abi_encode(fn_sig: `str`, params: `str`)"),
-        example: Some("// abi encode without function selector:\n// 0x\n// 000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa96045\n// 0000000000000000000000000000000000000000000000000000000000000001\n \nabi_encode(\n  \"transfer(address,uint256)\",   // fn_sig\n  \"0xd8da6bf2..7aa96045, 1\"      // params\n)"),
-        desc: "ABI encodes the arguments and outputs the corresponding calldata without the function selector",
+        example: Some("// abi encode without function selector:\n// 0x\n// 000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa96045\n// 0000000000000000000000000000000000000000000000000000000000000001\n \nabi_encode(\n  \"transfer(address,uint256)\",   // fn_sig\n  0xd8da6bf2..7aa96045, 1        // values\n)"),
+        desc: "ABI encodes the arguments and outputs the corresponding calldata without the function selector.\nValues are typed: tuples are written `(a, b)`, arrays `[a, b]` and strings may contain commas. The legacy single string of comma separated values (`\"0xd8da..6045, 1\"`) is still accepted",
     },
     SearchItemData {
-        id: 25,
+        id: 27,
         command: "abi_encode_with_selector",
         c_type: CommandType::Function,
         alias: Some("abi_encode_with_sig"),
         params: Some("abi_encode_with_selector(fn_sig: `str`, params: `str`)"),
-        example: Some("// abi encode with function selector:\n// 0x\n// a9059cbb\n// 000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa96045\n// 0000000000000000000000000000000000000000000000000000000000000001\n \nabi_encode(\n  \"transfer(address,uint256)\",   // fn_sig\n  \"0xd8da6bf2..7aa96045, 1\"      // params\n)"),
-        desc: "ABI encodes the arguments and outputs the corresponding calldata with the function selector",
+        example: Some("// abi encode with function selector:\n// 0x\n// a9059cbb\n// 000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa96045\n// 0000000000000000000000000000000000000000000000000000000000000001\n \nabi_encode_with_sig(\n  \"transfer(address,uint256)\",   // fn_sig\n  0xd8da6bf2..7aa96045, 1        // values\n)"),
+        desc: "ABI encodes the arguments and outputs the corresponding calldata with the function selector.\nValues are typed, as for `abi_encode`: `abi_encode_with_sig(\"f((address,uint256)[],string)\", [(0x35..35, 1), (0x35..36, 2)], \"hello, world\")`",
     },
     SearchItemData {
-        id: 26,
+        id: 28,
         command: "abi_decode",
         c_type: CommandType::Function,
         alias: None,
         params: Some("abi_decode(fn_sig: `str`, calldata: `str`)"),
         example: Some("// abi decode:\n//   fn_selector: \"0xa9059cbb\"\n//   address: \"0xd8da6bf26964af9d7eed9e03e53415d37aa96045\"\n//   uint256: \"0x1\"\n \nabi_decode(\n  \"transfer(address,uint256)\",        // fn_sig\n  \"0xa9059cbb000000000000..0000001\"   // calldata\n)"),
-        desc: "Decodes calldata given a function signature. Automatically identifies the 8-byte function selectors if present.",
+        desc: "Decodes calldata given a function signature. Automatically identifies the 8-byte function selectors if present.\nWithout a signature (`abi_decode(calldata)`), every function of the offline signature database matching the selector is tried, and the candidates that re-encode to the exact calldata are ranked first.",
     },
     SearchItemData {
-        id: 27,
+        id: 29,
         command: "debug",
         c_type: CommandType::Function,
         alias: None,
@@ -293,7 +315,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Pretty prints calldata in 32-byte words. Automatically identifies 8-byte function selectors if present.",
     },
     SearchItemData {
-        id: 28,
+        id: 30,
         command: "uppercase",
         c_type: CommandType::Function,
         alias: Some("upper"),
@@ -302,7 +324,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Converts a string to upper case",
     },
     SearchItemData {
-        id: 29,
+        id: 31,
         command: "lowercase",
         c_type: CommandType::Function,
         alias: Some("lower"),
@@ -311,7 +333,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Converts a string to lower case",
     },
     SearchItemData {
-        id: 30,
+        id: 32,
         command: "len",
         c_type: CommandType::Function,
         alias: Some("chars"),
@@ -320,7 +342,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Returns the length of a string",
     },
     SearchItemData {
-        id: 31,
+        id: 33,
         command: "count",
         c_type: CommandType::Function,
         alias: None,
@@ -329,7 +351,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Counts occurrences of a substring within a string",
     },
     SearchItemData {
-        id: 32,
+        id: 34,
         command: "left_pad",
         c_type: CommandType::Function,
         alias: Some("lpad"),
@@ -338,7 +360,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Pads a string the to the left, with a zeros, to a specified length",
     },
     SearchItemData {
-        id: 33,
+        id: 35,
         command: "right_pad",
         c_type: CommandType::Function,
         alias: Some("rpad"),
@@ -347,7 +369,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Pads a string the to the right, with a zeros, to a specified length",
     },
     SearchItemData {
-        id: 34,
+        id: 36,
         command: "format_ether",
         c_type: CommandType::Function,
         alias: None,
@@ -356,7 +378,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Formats the input number with 18 decimal places. Since floating point math is not supported, outputs a string.",
     },
     SearchItemData {
-        id: 35,
+        id: 37,
         command: "format_uints",
         c_type: CommandType::Function,
         alias: None,
@@ -365,7 +387,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Formats the input number with a specified number of decimals. Since floating point math is not supported, outputs a string.",
     },
     SearchItemData {
-        id: 36,
+        id: 38,
         command: "unix",
         c_type: CommandType::Function,
         alias: None,
@@ -374,7 +396,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Bidirectional function:\n- Converts a unix timestamp to a human-readable date. Accepts a second argument for the date format.\n- Converts strings of comma-separated values or '%Y-%m-%d %H:%M:%S' format to a unix timestamp.",
     },
     SearchItemData {
-        id: 37,
+        id: 39,
         command: "get_price_from_tick",
         c_type: CommandType::Function,
         alias: Some("get_price, price_from_tick, price_at_tick"),
@@ -383,7 +405,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the price of a Uniswap V3 pool (in token0 or token1) given a tick value and the token decimals.",
     },
     SearchItemData {
-        id: 38,
+        id: 40,
         command: "get_tick_from_sqrt_ratio",
         c_type: CommandType::Function,
         alias: Some("get_tick, tick_from_sqrt_ratio, tick_from_sqrt_x96"),
@@ -392,7 +414,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the tick of a Uniswap V3 pool given a square root of price as a Q64.96.",
     },
     SearchItemData {
-        id: 39,
+        id: 41,
         command: "get_sqrt_ratio_from_tick",
         c_type: CommandType::Function,
         alias: Some("get_sqrt_ratio, get_sqrt_x96, sqrt_ratio_from_tick, sqrt_x96_from_tick"),
@@ -401,7 +423,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the square root of price as a Q64.96 give the tick of a Uniswap V3 pool.",
     },
     SearchItemData {
-        id: 40,
+        id: 42,
         command: "get_liquidity_from_total_amount1",
         c_type: CommandType::Function,
         alias: Some("get_liquidity, liquidity_from_total_amount1"),
@@ -411,7 +433,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
     },
     // TODO: doc examples
     SearchItemData {
-        id: 41,
+        id: 43,
         command: "get_amount0_from_range",
         c_type: CommandType::Function,
         alias: Some("get_amount0, amount0_from_range"),
@@ -420,7 +442,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the equivalent amount0 of a Uniswap V3 range given the pool's sqrtPrice, and the range's liquidity, sqrtPa, and sqrtPb.",
     },
     SearchItemData {
-        id: 42,
+        id: 44,
         command: "get_amount1_from_range",
         c_type: CommandType::Function,
         alias: Some("get_amount1, amount1_from_range"),
@@ -429,7 +451,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the equivalent amount1 of a Uniswap V3 range given the pool's sqrtPrice, and the range's liquidity, sqrtPa, and sqrtPb.",
     },
     SearchItemData {
-        id: 43,
+        id: 45,
         command: "get_token0",
         c_type: CommandType::Function,
         alias: Some("token0"),
@@ -438,7 +460,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Retuns the token0 of a Uniswap V3 pool.",
     },
     SearchItemData {
-        id: 44,
+        id: 46,
         command: "get_token1",
         c_type: CommandType::Function,
         alias: Some("token1"),
@@ -447,7 +469,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Retuns the token1 of a Uniswap V3 pool.",
     },
     SearchItemData {
-        id: 45,
+        id: 47,
         command: "get_quote_from_tick",
         c_type: CommandType::Function,
         alias: Some("get_quote, quote_from_tick, quote_at_tick"),
@@ -456,7 +478,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the quote of a Uniswap V3 pool (in token0 or token1) given a tick value and the token decimals.",
     },
     SearchItemData {
-        id: 46,
+        id: 48,
         command: "get_sqrt_ratio_from_price",
         c_type: CommandType::Function,
         alias: Some("get_sqrt_x96_from_price, sqrt_from_price"),
@@ -465,7 +487,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the sqrtPrice of a Uniswap V3 pool given a price (ratio between tokens, accounting for its decimals).",
     },
     SearchItemData {
-        id: 47,
+        id: 49,
         command: "get_tick_from_price",
         c_type: CommandType::Function,
         alias: Some("tick_from_price"),
@@ -474,7 +496,7 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         desc: "Computes the tick of a Uniswap V3 pool given a price (ratio between tokens, accounting for its decimals).\n Note that the tick may need to be adjusted basead on the pool's tick spacing.",
     },
     SearchItemData {
-        id: 48,
+        id: 50,
         command: "get_pool_tick",
         c_type: CommandType::Function,
         alias: Some("get_tick_from_spacing, get_tick_with_spacing"),
@@ -482,6 +504,492 @@ pub static SEARCH_ITEMS: &[SearchItemData; 49] = &[
         example: Some("get_pool_tick(-887272, 10)   // tick (-887270)\nget_pool_tick(887272, 200)   // tick (887200)"),
         desc: "Computes the tick of a Uniswap V3 pool given a price (ratio between tokens, accounting for its decimals).\n Note that the tick may need to be adjusted basead on the pool's tick spacing.",
     },
+    SearchItemData {
+        id: 51,
+        command: "is_checksummed",
+        c_type: CommandType::Function,
+        alias: Some("is_checksum"),
+        params: Some("is_checksummed(addr: `str`, chain_id?: `uint64`)"),
+        example: Some("// valid EIP-55 checksum ('true')\nis_checksummed(\"0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045\")\n \n// invalid EIP-1191 checksum for chain 30 ('false')\nis_checksummed(\"0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045\", 30)"),
+        desc: "Checks whether a quoted address is correctly checksummed.\nWhen a `chain_id` is provided, it validates the chain-specific EIP-1191 checksum instead.",
+    },
+    SearchItemData {
+        id: 52,
+        command: "namehash",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("namehash(name: `str`)"),
+        example: Some("// ENS node (0xee6c4522aab0003e8d14cd40a6af439055fd2577951148c14b6cea9a53475835)\nnamehash(\"vitalik.eth\")"),
+        desc: "Computes the ENS namehash of a name, as defined in EIP-137.\nASCII names are lowercased before hashing, so `Vitalik.ETH` and `vitalik.eth` produce the same node. Names with other characters are rejected: normalize them (ENSIP-15) beforehand.",
+    },
+    SearchItemData {
+        id: 53,
+        command: "labelhash",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("labelhash(label: `str`)"),
+        example: Some("// ENS label hash (0xaf2caa1c2ca1d027f1ac823b529d0a67cd144264b2789fa2ea4d63a67c7103cc)\nlabelhash(\"vitalik\")"),
+        desc: "Computes the ENS labelhash (keccak256) of a single normalized label.",
+    },
+    SearchItemData {
+        id: 54,
+        command: "array",
+        c_type: CommandType::Input,
+        alias: None,
+        params: None,
+        example: Some("[1, 0x1234, \"dog\"]        // flat array\n[\"cat\", [\"dog\", [0x01]]]   // nested arrays"),
+        desc: "Arrays are wrapped in square brackets and accept any other input type, including nested arrays.\nThey can be passed to functions that expect lists of values, such as `rlp_encode`.",
+    },
+    SearchItemData {
+        id: 55,
+        command: "rlp_encode",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("rlp_encode(value: `uint256` | `str` | `array`)"),
+        example: Some("rlp_encode(\"dog\")            // RLP string (0x83646f67)\nrlp_encode(1024)             // RLP integer (0x820400)\nrlp_encode([\"cat\", \"dog\"])   // RLP list (0xc88363617483646f67)"),
+        desc: "RLP encodes a value. Numbers are encoded as big-endian integers without leading zeros,\nhex literals and `0x` prefixed strings as raw bytes (keeping their leading zeros), other strings as UTF-8 bytes, and arrays as (nested) RLP lists.",
+    },
+    SearchItemData {
+        id: 56,
+        command: "rlp_decode",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("rlp_decode(rlp: `str`)"),
+        example: Some("// RLP list with 2 items:\n//   offset: 0, length: 8, list: [\n//     { offset: 1, length: 3, bytes: \"0x636174\" },\n//     { offset: 5, length: 3, bytes: \"0x646f67\" },\n//   ]\nrlp_decode(\"0xc88363617483646f67\")"),
+        desc: "Decodes an RLP payload into a nested structure. Each item is annotated with its byte offset in the payload and its length.\nNon-canonical encodings and trailing bytes are rejected.",
+    },
+    SearchItemData {
+        id: 57,
+        command: "decode_tx",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("decode_tx(\u{00a0}\u{00a0}\u{00a0}\n\u{00a0}\u{00a0}raw_tx: `str`,\n\u{00a0}\u{00a0}fn_sig?: `str`\n)"),
+        example: Some("// { type: 0, chainId: 1, nonce: 9, ..., from: \"0x9d8A...5A4F\" }\ndecode_tx(\"0xf86c0985...3b6d83\")\n// also decodes the calldata\ndecode_tx(\"0x02f8b0...\", \"transfer(address,uint256)\")"),
+        desc: "Decodes a signed raw transaction: legacy (including EIP-155), EIP-2930, EIP-1559, EIP-4844 (with or without blob sidecars) and EIP-7702.\nShows the chain id, nonce, gas fields, to, value, access list, blob hashes and authorization list (with recovered authorities), plus the recovered sender and the transaction hash.",
+    },
+    SearchItemData {
+        id: 58,
+        command: "tx",
+        c_type: CommandType::Function,
+        alias: Some("transaction"),
+        params: Some("tx(\u{00a0}\u{00a0}\u{00a0}\n\u{00a0}\u{00a0}to=`address`,\n\u{00a0}\u{00a0}value=`uint256`,\n\u{00a0}\u{00a0}data=`bytes`,\n\u{00a0}\u{00a0}nonce=`uint256`,\n\u{00a0}\u{00a0}chain_id=`uint256`,\n\u{00a0}\u{00a0}gas=`uint256`,\n\u{00a0}\u{00a0}max_fee=`uint256`,\n\u{00a0}\u{00a0}priority_fee=`uint256`,\n\u{00a0}\u{00a0}gas_price?=`uint256`,\n\u{00a0}\u{00a0}key?=`bytes32`\n)"),
+        example: Some("// { type: 2, chainId: 1, ..., unsignedTx: \"0x02ef...\", signingHash: \"0x...\" }\ntx(to=0xd8dA...6045, value=1 ether, nonce=0, chain_id=1, max_fee=30 gwei, priority_fee=1 gwei)\n// legacy EIP-155 tx, signed\ntx(to=0x3535...3535, value=1 ether, nonce=9, chain_id=1, gas_price=20 gwei, key=0x4646...4646)"),
+        desc: "Builds an unsigned EIP-1559 transaction from named fields (legacy when `gas_price` or `type=0` is given), returning its RLP encoding and signing hash.\nWhen a private key is supplied, it also returns the signed raw transaction, its hash and the sender.\nDefaults: nonce, value and fees to 0, gas to the intrinsic gas of the transaction (21000 plus the cost of its data) and data to empty. `to` may be omitted for contract creation.",
+    },
+    SearchItemData {
+        id: 59,
+        command: "amount",
+        c_type: CommandType::Input,
+        alias: None,
+        params: None,
+        example: Some("1 ether         // 1000000000000000000\n1.5 gwei + 1    // 1500000001"),
+        desc: "A number followed by an ether unit (wei, gwei, ether...) is evaluated in wei.",
+    },
+    SearchItemData {
+        id: 60,
+        command: "sign",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("sign(hash: `bytes32`, private_key: `bytes32`)"),
+        example: Some("// 0x28ef...6276 67cb...6d83 1b (r || s || v)\nsign(0xdaf5...8e53, 0x4646...4646)"),
+        desc: "Signs a 32-byte hash with a private key (no prefix is added), returning the 65-byte `r || s || v` signature with a low `s` value.\nKeys are kept in memory only: blocks holding key material are left out of exported notebooks.",
+    },
+    SearchItemData {
+        id: 61,
+        command: "ecrecover",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("ecrecover(hash: `bytes32`, signature: `bytes`)\necrecover(\u{00a0}\u{00a0}\u{00a0}\n\u{00a0}\u{00a0}hash: `bytes32`,\n\u{00a0}\u{00a0}v: `uint8`,\n\u{00a0}\u{00a0}r: `bytes32`,\n\u{00a0}\u{00a0}s: `bytes32`\n)"),
+        example: Some("// 0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F\necrecover(0xdaf5...8e53, 0x28ef...6d831b)\necrecover(0xdaf5...8e53, 27, 0x28ef...6276, 0x67cb...6d83)"),
+        desc: "Recovers the signer of a hash from a 65-byte, 64-byte (EIP-2098) or split signature.\nv may be 0/1 or 27/28. Like the precompile, high `s` values are accepted.",
+    },
+    SearchItemData {
+        id: 62,
+        command: "pubkey",
+        c_type: CommandType::Function,
+        alias: Some("public_key"),
+        params: Some("pubkey(private_key: `bytes32`)"),
+        example: Some("// 0x04 || x || y\npubkey(0x4646...4646)"),
+        desc: "Derives the uncompressed public key of a private key.",
+    },
+    SearchItemData {
+        id: 63,
+        command: "address_of",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("address_of(key: `bytes`)"),
+        example: Some("// 0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F\naddress_of(0x4646...4646)"),
+        desc: "Derives the address of a private key (32 bytes) or of a public key (compressed, uncompressed or raw 64 bytes).",
+    },
+    SearchItemData {
+        id: 64,
+        command: "split_sig",
+        c_type: CommandType::Function,
+        alias: Some("split_signature"),
+        params: Some("split_sig(signature: `bytes`)"),
+        example: Some("// { r: \"0x28ef...\", s: \"0x67cb...\", v: 27, yParity: 0, highS: false, compact: \"0x28ef...\" }\nsplit_sig(0x28ef...6d831b)"),
+        desc: "Splits a 65-byte or 64-byte (EIP-2098) signature into r, s and v, flags malleable (high `s`) signatures and shows its compact form.",
+    },
+    SearchItemData {
+        id: 65,
+        command: "compact_sig",
+        c_type: CommandType::Function,
+        alias: Some("to_eip2098"),
+        params: Some("compact_sig(signature: `bytes`)"),
+        example: Some("compact_sig(0x28ef...6d831b)"),
+        desc: "Converts a signature into its 64-byte EIP-2098 compact form (`r || yParity << 255 | s`). Requires a low `s` value.",
+    },
+    SearchItemData {
+        id: 66,
+        command: "normalize_sig",
+        c_type: CommandType::Function,
+        alias: Some("normalize_s"),
+        params: Some("normalize_sig(signature: `bytes`)"),
+        example: Some("normalize_sig(0x28ef...d3be1c)"),
+        desc: "Returns the low `s` equivalent of a malleable signature (`s' = n - s`, flipping v), as required by EIP-2.",
+    },
+    SearchItemData {
+        id: 67,
+        command: "eip191_hash",
+        c_type: CommandType::Function,
+        alias: Some("hash_message"),
+        params: Some("eip191_hash(message: `str`)"),
+        example: Some("// 0x50b2c43fd39106bafbba0da34fc430e1f91e3c96ea2acee2bc34119f92b37750\neip191_hash(\"hello\")\n// hex literals are hashed as bytes, as written\neip191_hash(0xc52c...371e)"),
+        desc: "Computes the EIP-191 `personal_sign` digest of a message: `keccak256(\"\\x19Ethereum Signed Message:\\n\" || len(message) || message)`.",
+    },
+    SearchItemData {
+        id: 68,
+        command: "eip712_hash",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("eip712_hash(typed_data: `json`)"),
+        example: Some("// paste an eth_signTypedData_v4 payload in a block labeled `mail`, then:\n// { domain: { encodeType, typeHash, separator }, primaryType, types: [...], structHash, digest }\neip712_hash(mail)"),
+        desc: "Computes the EIP-712 digest of typed data, showing the domain separator, the struct hash and the `encodeType` string and type hash of every type involved, to debug mismatching signatures.",
+    },
+    SearchItemData {
+        id: 69,
+        command: "json",
+        c_type: CommandType::Input,
+        alias: None,
+        params: None,
+        example: Some("{ \"types\": { ... }, \"primaryType\": \"Mail\", \"domain\": { ... }, \"message\": { ... } }"),
+        desc: "JSON objects and arrays can be pasted into a block, and then passed to functions by referencing the block's label.",
+    },
+    SearchItemData {
+        id: 70,
+        command: "erc2612_permit_digest",
+        c_type: CommandType::Function,
+        alias: Some("permit_digest"),
+        params: Some("erc2612_permit_digest(\u{00a0}\u{00a0}\u{00a0}\n\u{00a0}\u{00a0}token: `address`,\n\u{00a0}\u{00a0}name: `str`,\n\u{00a0}\u{00a0}version: `str`,\n\u{00a0}\u{00a0}chain_id: `uint256`,\n\u{00a0}\u{00a0}owner: `address`,\n\u{00a0}\u{00a0}spender: `address`,\n\u{00a0}\u{00a0}value: `uint256`,\n\u{00a0}\u{00a0}nonce: `uint256`,\n\u{00a0}\u{00a0}deadline: `uint256`\n)"),
+        example: Some("erc2612_permit_digest(0xA0b8...eB48, \"USD Coin\", \"2\", 1, 0xd8dA...6045, 0x0000...8BA3, 1000000, 0, max_uint)"),
+        desc: "Computes the EIP-712 digest of an ERC-2612 `permit`, with the same intermediate values as `eip712_hash`.",
+    },
+    SearchItemData {
+        id: 71,
+        command: "permit2_single_digest",
+        c_type: CommandType::Function,
+        alias: Some("permit_single_digest"),
+        params: Some("permit2_single_digest(\u{00a0}\u{00a0}\u{00a0}\n\u{00a0}\u{00a0}chain_id: `uint256`,\n\u{00a0}\u{00a0}token: `address`,\n\u{00a0}\u{00a0}amount: `uint160`,\n\u{00a0}\u{00a0}expiration: `uint48`,\n\u{00a0}\u{00a0}nonce: `uint48`,\n\u{00a0}\u{00a0}spender: `address`,\n\u{00a0}\u{00a0}sig_deadline: `uint256`\n)"),
+        example: Some("permit2_single_digest(1, 0xA0b8...eB48, 1000000, 1700000000, 0, 0x3fC9...7FAD, 1700000000)"),
+        desc: "Computes the EIP-712 digest of a Permit2 `PermitSingle` allowance, signed for the canonical Permit2 deployment.",
+    },
+    SearchItemData {
+        id: 72,
+        command: "permit2_transfer_digest",
+        c_type: CommandType::Function,
+        alias: Some("permit_transfer_from_digest"),
+        params: Some("permit2_transfer_digest(\u{00a0}\u{00a0}\u{00a0}\n\u{00a0}\u{00a0}chain_id: `uint256`,\n\u{00a0}\u{00a0}token: `address`,\n\u{00a0}\u{00a0}amount: `uint256`,\n\u{00a0}\u{00a0}nonce: `uint256`,\n\u{00a0}\u{00a0}deadline: `uint256`,\n\u{00a0}\u{00a0}spender: `address`\n)"),
+        example: Some("permit2_transfer_digest(1, 0xA0b8...eB48, 1000000, 0, 1700000000, 0x3fC9...7FAD)"),
+        desc: "Computes the EIP-712 digest of a Permit2 `PermitTransferFrom` signature transfer, signed for the canonical Permit2 deployment.",
+    },
+    SearchItemData {
+        id: 73,
+        command: "safe_tx_hash",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("safe_tx_hash(\u{00a0}\u{00a0}\u{00a0}\n\u{00a0}\u{00a0}safe: `address`,\n\u{00a0}\u{00a0}chain_id: `uint256`,\n\u{00a0}\u{00a0}to: `address`,\n\u{00a0}\u{00a0}value: `uint256`,\n\u{00a0}\u{00a0}data: `bytes`,\n\u{00a0}\u{00a0}operation: `uint8`,\n\u{00a0}\u{00a0}safe_tx_gas?: `uint256`,\n\u{00a0}\u{00a0}base_gas?: `uint256`,\n\u{00a0}\u{00a0}gas_price?: `uint256`,\n\u{00a0}\u{00a0}gas_token?: `address`,\n\u{00a0}\u{00a0}refund_receiver?: `address`,\n\u{00a0}\u{00a0}nonce: `uint256`\n)"),
+        example: Some("// without gas refund fields (defaulting to zero)\nsafe_tx_hash(0x1234...5678, 1, 0xd8dA...6045, 1 ether, \"0x\", 0, 5)"),
+        desc: "Computes the Safe (1.3.0 and later) transaction hash that owners sign.\nThe gas refund fields (safe_tx_gas, base_gas, gas_price, gas_token and refund_receiver) can be omitted together, in which case they default to zero.",
+    },
+    SearchItemData {
+        id: 74,
+        command: "user_op_hash",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("user_op_hash(\u{00a0}\u{00a0}\u{00a0}\n\u{00a0}\u{00a0}user_op: `json`,\n\u{00a0}\u{00a0}entry_point: `address`,\n\u{00a0}\u{00a0}chain_id: `uint256`\n)"),
+        example: Some("// paste the user operation in a block labeled `op`, then:\n// { version: \"0.7\", initCode, accountGasLimits, gasFees, paymasterAndData, encoded, userOpHash }\nuser_op_hash(op, 0x0000000071727De22E5E9d8BAf0edAc6f37da032, 1)"),
+        desc: "Computes the ERC-4337 `userOpHash`, as returned by `EntryPoint.getUserOpHash`.\nSupports v0.6 `UserOperation`s, v0.7 `PackedUserOperation`s and the unpacked v0.7 RPC format (`factory`, `callGasLimit`, `paymaster`...), which is packed first.\nOperations with `initCode` or `paymasterAndData` but no `accountGasLimits` are hashed as v0.6 ones.",
+    },
+    SearchItemData {
+        id: 75,
+        command: "pack_gas_limits",
+        c_type: CommandType::Function,
+        alias: Some("pack_gas_fees, pack_uint128"),
+        params: Some("pack_gas_limits(verification_gas_limit: `uint128`, call_gas_limit: `uint128`)\npack_gas_fees(max_priority_fee_per_gas: `uint128`, max_fee_per_gas: `uint128`)"),
+        example: Some("// 0x000000000000000000000000000186a000000000000000000000000000030d40\npack_gas_limits(100000, 200000)"),
+        desc: "Packs two uint128 values into the v0.7 `accountGasLimits` or `gasFees` word (`high << 128 | low`).",
+    },
+    SearchItemData {
+        id: 76,
+        command: "unpack_gas_limits",
+        c_type: CommandType::Function,
+        alias: Some("unpack_gas_fees"),
+        params: Some("unpack_gas_limits(account_gas_limits: `bytes32`)\nunpack_gas_fees(gas_fees: `bytes32`)"),
+        example: Some("// { verificationGasLimit: \"100000\", callGasLimit: \"200000\" }\nunpack_gas_limits(0x0000...86a0 0000...0d40)"),
+        desc: "Splits a v0.7 `accountGasLimits` or `gasFees` word into its two uint128 values.",
+    },
+    SearchItemData {
+        id: 77,
+        command: "auth_hash",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("auth_hash(chain_id: `uint256`, address: `address`, nonce: `uint64`)"),
+        example: Some("auth_hash(1, 0x63c0...3B32, 0)"),
+        desc: "Computes the EIP-7702 authorization hash signed by an EOA to delegate to `address`: `keccak256(0x05 || rlp([chain_id, address, nonce]))`.",
+    },
+    SearchItemData {
+        id: 78,
+        command: "decode",
+        c_type: CommandType::Function,
+        alias: Some("decode_calldata"),
+        params: Some("decode(abi: `json`, calldata: `str`)"),
+        example: Some("// paste the contract's JSON ABI, or a list of\n// human-readable signatures, in a block labeled `erc20`:\n// [\"function transfer(address to, uint256 amount)\"]\n// { function: \"transfer(address,uint256)\",\n//   selector: \"0xa9059cbb\",\n//   args: { \"address to\": \"0xd8dA...6045\", \"uint256 amount\": \"1\" } }\ndecode(erc20, \"0xa9059cbb000000000000..0000001\")"),
+        desc: "Decodes calldata against a full ABI, picking the function from the selector. Arguments are keyed by their type and name, and tuples are decoded as objects named after their components.\nThe ABI can be a JSON ABI, a compiler artifact with an `abi` field or a list of human-readable signatures.",
+    },
+    SearchItemData {
+        id: 79,
+        command: "decode_log",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("decode_log(\u{00a0}\u{00a0}\u{00a0}\n\u{00a0}\u{00a0}event: `str` | `json`,\n\u{00a0}\u{00a0}topics: `bytes32[]`,\n\u{00a0}\u{00a0}data: `str`\n)"),
+        example: Some("// { event: \"Transfer(address,address,uint256)\",\n//   topic0: \"0xddf2...b3ef\",\n//   args: {\n//     \"address indexed from\": \"0xd8dA...6045\",\n//     \"address indexed to\": \"0x3535...3535\",\n//     \"uint256 value\": \"1000\" } }\ndecode_log(\n  \"Transfer(address indexed from, address indexed to, uint256 value)\",\n  [0xddf2...b3ef, 0x0000...6045, 0x0000...3535],\n  1000\n)"),
+        desc: "Decodes an event log given its signature or an ABI block, picking the event from topic0 (anonymous events are matched by their parameters).\nIndexed dynamic types (strings, bytes, arrays and structs) can't be recovered, so their hash is shown instead.",
+    },
+    SearchItemData {
+        id: 80,
+        command: "decode_logs",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("decode_logs(receipt: `json`)"),
+        example: Some("// paste an eth_getTransactionReceipt response in a block\n// labeled `receipt` and the ABIs in other blocks, then:\n// [{ address, logIndex, event, topic0, args }, ...]\ndecode_logs(receipt)"),
+        desc: "Decodes every log of a transaction receipt whose topic0 matches an event of an ABI (JSON or human-readable) pasted in the notebook. Unknown logs are kept as they are.",
+    },
+    SearchItemData {
+        id: 81,
+        command: "decode_revert",
+        c_type: CommandType::Function,
+        alias: Some("decode_error"),
+        params: Some("decode_revert(data: `str`, abi?: `json`)"),
+        example: Some("// { error: \"Panic(uint256)\", selector: \"0x4e487b71\",\n//   code: \"0x11\",\n//   reason: \"arithmetic overflow or underflow ...\" }\ndecode_revert(\"0x4e487b71000000...0000011\")\n \n// with the contract's ABI in a block labeled `vault`\ndecode_revert(\"0x1176bd96000000...0000007\", vault)"),
+        desc: "Decodes the data of a reverted call: `Error(string)` messages, `Panic(uint256)` codes (with an explanation of the code) and custom errors, from the given ABI or the errors of widely used contracts (OpenZeppelin, Uniswap, Permit2...).\nRevert data bubbled up inside a `bytes` argument or an `Error(string)` (multicall, try/catch) is decoded under `inner`. A pasted JSON-RPC error with a `data` field is accepted as well.",
+    },
+    SearchItemData {
+        id: 82,
+        command: "guess_selector",
+        c_type: CommandType::Function,
+        alias: Some("fn_from_selector"),
+        params: Some("guess_selector(selector: `bytes4`)"),
+        example: Some("// [\"transfer(address,uint256)\",\n//  \"many_msg_babbage(bytes1)\", ...]\nguess_selector(0xa9059cbb)"),
+        desc: "Looks up a function selector in the offline signature database, returning every candidate signature.",
+    },
+    SearchItemData {
+        id: 83,
+        command: "guess_event",
+        c_type: CommandType::Function,
+        alias: Some("event_from_topic"),
+        params: Some("guess_event(topic0: `bytes32`)"),
+        example: Some("// [\"Transfer(address,address,uint256)\"]\nguess_event(0xddf252ad...f523b3ef)"),
+        desc: "Looks up an event topic0 in the offline signature database, returning every candidate signature.",
+    },
+    SearchItemData {
+        id: 84,
+        command: "guess_error",
+        c_type: CommandType::Function,
+        alias: Some("error_from_selector"),
+        params: Some("guess_error(selector: `bytes4`)"),
+        example: Some("// [\"ERC20InsufficientBalance(address,uint256,uint256)\"]\nguess_error(0xe450d38c)"),
+        desc: "Looks up a custom error selector in the offline signature database, returning every candidate signature.",
+    },
+    SearchItemData {
+        id: 85,
+        command: "abi_encode_packed",
+        c_type: CommandType::Function,
+        alias: Some("encode_packed"),
+        params: Some("abi_encode_packed(types: `str`, values: `str`)"),
+        example: Some("// 0x\n// d8da6bf26964af9d7eed9e03e53415d37aa96045\n// 0000000000000000000000000000000000000000000000000000000000000001\n \nkeccak256(abi_encode_packed(\n  \"(address,uint256)\",          // types\n  \"0xd8da6bf2..7aa96045, 1\"     // values\n))"),
+        desc: "Encodes the values like Solidity's `abi.encodePacked`, ie: for Merkle leaves, signature payloads or CREATE2 salts. Values take their minimal size and dynamic types are encoded in place without their length, while array elements are padded to 32 bytes.\nStructs, nested arrays and arrays of dynamic types are rejected, as solc does.",
+    },
+    SearchItemData {
+        id: 86,
+        command: "abi_encode_params",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("abi_encode_params(types: `str`, values: `str`)"),
+        example: Some("// 0x\n// 0000000000000000000000000000000000000000000000000000000000000001\n// 0000000000000000000000000000000000000000000000000000000000000040\n// 0000000000000000000000000000000000000000000000000000000000000002\n// 1234000000000000000000000000000000000000000000000000000000000000\n \nabi_encode_params(\"(uint256,bytes)\", \"1, 0x1234\")"),
+        desc: "Encodes the values like Solidity's `abi.encode(a, b, ..)`: the head/tail encoding of the values, without a function selector.",
+    },
+    SearchItemData {
+        id: 87,
+        command: "inspect_calldata",
+        c_type: CommandType::Function,
+        alias: Some("inspect"),
+        params: Some("inspect_calldata(fn_sig: `str`, calldata: `str`)"),
+        example: Some("// 0x0000  ..0007  uint256 id\n// 0x0020  ..0040  offset of bytes data → 0x40\n// 0x0040  ..0002  length of bytes data: 2 bytes\n// 0x0060  1234..  bytes data data + padding\n \ninspect_calldata(\n  \"f(uint256 id, bytes data)\",   // fn_sig\n  \"0xcdc9dc790000..1234\"         // calldata\n)"),
+        desc: "Renders each 32-byte word of the calldata with its offset and what it encodes: the selector, the head slot of a parameter, an offset pointer, a length word, a tail element or padding. Words are colored after the parameter they belong to.\nOffsets are followed as encoded, which helps when hand-crafting or auditing dynamic encodings. Without a signature (`inspect_calldata(calldata)`), the function is looked up in the offline signature database.",
+    },
+    SearchItemData {
+        id: 88,
+        command: "multicall_encode",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("multicall_encode(calls: `[(address, bool, bytes)]`)"),
+        example: Some("// Multicall3 aggregate3 calldata (0x82ad56cb..)\nmulticall_encode([\n  (dai, true, \"0x70a08231..7aa96045\"),\n  (dai, false, 0x18160ddd)\n])\n \n// tryAggregate(requireSuccess, calls) (0xbce38bd7..)\nmulticall_encode(false, [(dai, 0x18160ddd)])"),
+        desc: "Encodes a batch of `(target, allowFailure, calldata)` calls for Multicall3's `aggregate3`. With a `requireSuccess` flag first and `(target, calldata)` calls, encodes `tryAggregate` instead.",
+    },
+    SearchItemData {
+        id: 89,
+        command: "multicall_decode",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("multicall_decode(returndata: `str`, signatures: `[str]`)"),
+        example: Some("// [{ function: \"balanceOf(address)(uint256)\", success: true,\n//    result: { \"uint256 [0]\": \"5\" } },\n//  { ..., success: false,\n//    revert: { error: \"Error(string)\", reason: \"..\" } }]\n \nmulticall_decode(result, [\n  \"balanceOf(address)(uint256)\",\n  \"totalSupply()(uint256)\"\n])"),
+        desc: "Unpacks the `(bool success, bytes returnData)[]` returned by `aggregate3` and `tryAggregate` (or a pasted `eth_call` response), then decodes the return data of each call with its signature, written `fn(inputs)(outputs)` or `fn(inputs) returns (outputs)`. The revert data of failed calls is decoded instead, and calls without a signature keep their raw return data.",
+    },
+    SearchItemData {
+        id: 90,
+        command: "interface_id",
+        c_type: CommandType::Function,
+        alias: Some("erc165_id"),
+        params: Some("interface_id(...fn_sigs: `str`)\ninterface_id(abi: `json`)"),
+        example: Some("// ERC-165 interface id (0x01ffc9a7)\ninterface_id(\"supportsInterface(bytes4)\")\n \n// from a pasted interface or imported artifact (0x80ac58cd)\ninterface_id(IERC721)"),
+        desc: "Returns the ERC-165 interface identifier: the XOR of the selectors of the functions of an interface. Takes the function signatures (or a list of them), or an ABI block whose functions all belong to the interface.",
+    },
+    SearchItemData {
+        id: 91,
+        command: "selector_collisions",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("selector_collisions(abi_a: `json`, abi_b: `json`)"),
+        example: Some("// [{ selector: \"0x42966c68\",\n//    a: \"collate_propagate_storage(bytes16)\",\n//    b: \"burn(uint256)\" }]\nselector_collisions(proxy, implementation)"),
+        desc: "Lists the functions of two ABIs (or lists of signatures) that share a 4-byte selector, ie: the functions of a transparent proxy that shadow those of its implementation.",
+    },
+    SearchItemData {
+        id: 92,
+        command: "canonical_signature",
+        c_type: CommandType::Function,
+        alias: Some("canonical_sig"),
+        params: Some("canonical_signature(fn_sig: `str`)"),
+        example: Some("// fill((address,uint256),bytes)\ncanonical_signature(\n  \"function fill((address maker, uint amount) memory order, bytes calldata sig) external\"\n)"),
+        desc: "Returns the canonical form of a function signature, as hashed for its selector: parameter names, data locations and modifiers are dropped, type aliases are expanded (`uint` to `uint256`) and struct tuples are flattened to their component types.",
+    },
+    SearchItemData {
+        id: 93,
+        command: "disassemble",
+        c_type: CommandType::Function,
+        alias: Some("disasm"),
+        params: Some("disassemble(bytecode: `str`)"),
+        example: Some("// sel  0xd09de08a increment() → 0x0022\n// runtime @ 0x001a (45 bytes)\n// 0x0000 PUSH1 0x80\n// 0x0002 PUSH1 0x40\n// 0x0004 MSTORE\n// ...\ndisassemble(Counter.bytecode)"),
+        desc: "Disassembles EVM bytecode into its instructions, with their program counter and `PUSH` immediates (flagged when the code ends within them), split into basic blocks at jumps, halting instructions and `JUMPDEST`s.\nCreation code is split from the runtime code it returns (constructor arguments following it are listed as `args`), and the solc CBOR metadata trailer is left out of the listing. The selectors of the dispatcher are resolved with the ABIs of the notebook, or guessed from the signature database (marked with `?`).",
+    },
+    SearchItemData {
+        id: 94,
+        command: "evm",
+        c_type: CommandType::Function,
+        alias: Some("execute"),
+        params: Some("evm(\u{00a0}\u{00a0}\u{00a0}\n\u{00a0}\u{00a0}code=`bytes`,\n\u{00a0}\u{00a0}calldata?=`bytes`,\n\u{00a0}\u{00a0}caller?=`address`,\n\u{00a0}\u{00a0}address?=`address`,\n\u{00a0}\u{00a0}value?=`uint256`,\n\u{00a0}\u{00a0}gas?=`uint256`,\n\u{00a0}\u{00a0}storage?=`json | [(slot, value)]`\n)"),
+        example: Some("// { success: true, output: \"0x..2a\", gasUsed: 43899, logs: [..],\n//   storage: [{ slot: \"0x0\", from: \"0x0\", to: \"0x7\", .. }],\n//   trace: [{ pc: 0, op: \"PUSH0\", gas: 29978860, depth: 1, stack: [] }, ..] }\nevm(code=\"0x5f355f55602a5f5260205fa060205ff3\", calldata=abi_encode(\"(uint256)\", 7))\n \n// runtime code of an imported artifact, with a storage prestate\nevm(code=Counter.deployedBytecode, calldata=Counter.increment(), storage=[(0, 41)])"),
+        desc: "Runs bytecode in an in-memory EVM (Cancun), as a call from `caller` to an account holding the code, without a node.\nReturns the output (or the decoded revert data, or the reason execution halted), the gas used by the transaction (including the 21000 intrinsic gas), the logs, the storage writes and a trace of the first 5000 steps.\nDefaults: caller and address to the first Anvil account and contract, value to 0, gas to 30M and storage to empty. `storage` is a pasted json object of slots, or a list of `(slot, value)`.",
+    },
+    SearchItemData {
+        id: 95,
+        command: "metadata",
+        c_type: CommandType::Function,
+        alias: Some("decode_metadata"),
+        params: Some("metadata(runtime_bytecode: `str`)"),
+        example: Some("// { solc: \"0.8.24\", ipfs: \"QmZtnFaddFtzGNT8BxdHVbQrhSFdq1pWxud5z4fA4kxfDt\", length: 53 }\nmetadata(Counter.deployedBytecode)"),
+        desc: "Decodes the CBOR metadata solc appends to the runtime code: the compiler version, the IPFS hash of the metadata file (as a CIDv0) or its Swarm hash (`bzzr0`, `bzzr1`), and the `experimental` flag.\nPrerelease builds record their full version string.",
+    },
+    SearchItemData {
+        id: 96,
+        command: "constructor_args",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("constructor_args(\u{00a0}\u{00a0}\u{00a0}\n\u{00a0}\u{00a0}creation_tx_input: `str`,\n\u{00a0}\u{00a0}creation_bytecode: `str`,\n\u{00a0}\u{00a0}abi: `json | str`\n)\nconstructor_args(creation_tx_input: `str`, artifact: `json`)"),
+        example: Some("// { constructor: \"constructor(address,uint256)\",\n//   args: { \"address owner\": \"0xf39F..2266\", \"uint256 supply\": \"1000\" }, data: \"0x..\" }\nconstructor_args(tx_input, Token.bytecode, Token)\n \n// the creation code and ABI of an imported artifact\nconstructor_args(tx_input, Token)"),
+        desc: "Strips the creation code from the input of a creation transaction and decodes the constructor arguments that follow it with the ABI.\nCreation code that only differs in its metadata hashes (ie: compiled from another path) is accepted, and flagged as `metadataMismatch`.",
+    },
+    SearchItemData {
+        id: 97,
+        command: "sha256",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("sha256(data: `str | uint256`)"),
+        example: Some("// 0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\nsha256(\"abc\")"),
+        desc: "SHA-256 hash, as computed by the precompile at `0x02`.\nHex strings and literals are hashed as bytes, other strings as UTF-8 and computed numbers as 32-byte words.",
+    },
+    SearchItemData {
+        id: 98,
+        command: "ripemd160",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("ripemd160(data: `str | uint256`)"),
+        example: Some("// 0x0000000000000000000000008eb208f7e05d987a9b044a8e98c6b087f15a0bfc\nripemd160(\"abc\")"),
+        desc: "RIPEMD-160 hash, as returned by the precompile at `0x03`: left-padded to 32 bytes.\nHex strings and literals are hashed as bytes, other strings as UTF-8 and computed numbers as 32-byte words.",
+    },
+    SearchItemData {
+        id: 99,
+        command: "identity",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("identity(data: `str | uint256`)"),
+        example: Some("// 0x1234\nidentity(\"0x1234\")"),
+        desc: "Returns its input, like the precompile at `0x04` (the data copy contract).",
+    },
+    SearchItemData {
+        id: 100,
+        command: "modexp",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("modexp(base: `uint256 | str`, exponent: `uint256 | str`, modulus: `uint256 | str`)\nmodexp(input: `str`)"),
+        example: Some("// { result: \"0x00..18\", gas: 200 }\nmodexp(2, 10, 1000)\n \n// raw precompile input: the 3 lengths, then the operands\nmodexp(\"0x0000..0020\")"),
+        desc: "Modular exponentiation with the precompile at `0x05` (EIP-198), returning the result (as long as the modulus) and its gas cost (EIP-2565).\nNumbers are passed as 32-byte words, like Solidity does, and hex strings as they are, ie: 256-byte RSA moduli.",
+    },
+    SearchItemData {
+        id: 101,
+        command: "ec_add",
+        c_type: CommandType::Function,
+        alias: Some("ecadd"),
+        params: Some("ec_add(p1: `(uint256, uint256)`, p2: `(uint256, uint256)`)\nec_add(input: `str`)"),
+        example: Some("// (0x0306..cfd3, 0x15ed..a2c4)\nec_add((1, 2), (1, 2))"),
+        desc: "Adds two points of alt_bn128 with the precompile at `0x06` (EIP-196), returning the `(x, y)` coordinates of the sum.\nTakes the points, their coordinates as 4 numbers, or the raw precompile input. The point at infinity is `(0, 0)`.",
+    },
+    SearchItemData {
+        id: 102,
+        command: "ec_mul",
+        c_type: CommandType::Function,
+        alias: Some("ecmul"),
+        params: Some("ec_mul(p: `(uint256, uint256)`, scalar: `uint256`)\nec_mul(input: `str`)"),
+        example: Some("// (0x0306..cfd3, 0x15ed..a2c4)\nec_mul((1, 2), 2)"),
+        desc: "Multiplies a point of alt_bn128 by a scalar with the precompile at `0x07` (EIP-196), returning the `(x, y)` coordinates of the product.\nTakes the point and the scalar, 3 numbers, or the raw precompile input.",
+    },
+    SearchItemData {
+        id: 103,
+        command: "ec_pairing",
+        c_type: CommandType::Function,
+        alias: Some("ecpairing"),
+        params: Some("ec_pairing(input: `str`)\nec_pairing(pairs: `[uint256]`)"),
+        example: Some("// 1\nec_pairing(\"0x\")\n \n// a (G1, G2) pair, then the next ones\nec_pairing((a_x, a_y), (b_x_im, b_x_re, b_y_im, b_y_re), ..)"),
+        desc: "Pairing check on alt_bn128 with the precompile at `0x08` (EIP-197): returns `1` when the product of the pairings of the (G1, G2) pairs is one, `0` otherwise.\nTakes the raw precompile input, or the words of the pairs as numbers (possibly grouped). G2 coordinates have their imaginary part first.",
+    },
+    SearchItemData {
+        id: 104,
+        command: "blake2f",
+        c_type: CommandType::Function,
+        alias: None,
+        params: Some("blake2f(\u{00a0}\u{00a0}\u{00a0}\n\u{00a0}\u{00a0}rounds: `uint32`,\n\u{00a0}\u{00a0}h: `bytes64`,\n\u{00a0}\u{00a0}m: `bytes128`,\n\u{00a0}\u{00a0}t: `bytes16`,\n\u{00a0}\u{00a0}f: `bool`\n)\nblake2f(input: `str`)"),
+        example: Some("// 0xba80a53f..d4009923 (BLAKE2b-512 of \"abc\")\nblake2f(12, h, \"0x616263..00\", \"0x03000000000000000000000000000000\", true)"),
+        desc: "BLAKE2b compression function `F` with the precompile at `0x09` (EIP-152), returning the new state `h`.\n`h`, `m` and `t` are little-endian words, as in the 213-byte precompile input, which can also be passed as is.",
+    },
     // TODO: fix calculation
     // SearchItemData {
     //     id: 48,
@@ -1,5 +1,6 @@
 use super::card::{DetailCardComponent, SearchCardComponent};
-use super::docs::{SearchItemData, SEARCH_ITEMS};
+use super::docs::SearchItemData;
+use super::reference::search_items;
 
 use web_sys::{HtmlElement, HtmlInputElement, KeyboardEvent};
 use yew::prelude::*;
@@ -41,7 +42,7 @@ impl Component for SearchMenuComponent {
             expanded_index: None,
             start_index: 0,
             end_index: ITEMS_PER_PAGE + 1,
-            items: SEARCH_ITEMS.len(),
+            items: search_items().count(),
         }
     }
 
@@ -51,14 +52,13 @@ impl Component for SearchMenuComponent {
                 self.search_query = query.trim().to_lowercase();
                 self.focus_index = None;
 
-                let filtered_cards: Vec<_> = SEARCH_ITEMS
-                    .iter()
+                let filtered_cards: Vec<_> = search_items()
                     .filter(|item| {
-                        item.command.contains(&self.search_query)
+                        item.command.to_lowercase().contains(&self.search_query)
                             || item.c_type.to_string().contains(&self.search_query)
-                            || item
-                                .alias
-                                .map_or(false, |alias| alias.contains(&self.search_query))
+                            || item.alias.is_some_and(|alias| {
+                                alias.to_lowercase().contains(&self.search_query)
+                            })
                             || item.desc.to_lowercase().contains(&self.search_query)
                     })
                     .collect();
@@ -175,7 +175,7 @@ impl Component for SearchMenuComponent {
                     self.focus_index = None;
                     self.start_index = 0;
                     self.end_index = ITEMS_PER_PAGE + 1;
-                    self.items = SEARCH_ITEMS.len();
+                    self.items = search_items().count();
                     ctx.props().on_escape.emit(());
                 }
             }
@@ -194,26 +194,23 @@ impl Component for SearchMenuComponent {
         match &ctx.props().focus_ref {
             None => html! {<> </>},
             Some(focus) => {
-                // Filter based on command, type, alias, and description. Results are sorted by command, then type, then alias.
-                let mut filtered_cards: Vec<_> = SEARCH_ITEMS
-                    .iter()
+                // Filter based on command (or opcode mnemonic), type, alias (or opcode byte), and description. Results are sorted by command, then type, then alias.
+                let mut filtered_cards: Vec<_> = search_items()
                     .filter(|item| {
-                        item.command.contains(&self.search_query)
-                            || item
-                                .alias
-                                .map_or(false, |alias| alias.contains(&self.search_query))
+                        item.command.to_lowercase().contains(&self.search_query)
+                            || item.alias.is_some_and(|alias| {
+                                alias.to_lowercase().contains(&self.search_query)
+                            })
                     })
                     .collect();
-                let mut filtered_cards_type: Vec<_> = SEARCH_ITEMS
-                    .iter()
+                let mut filtered_cards_type: Vec<_> = search_items()
                     .filter(|item| {
                         item.c_type.to_string().contains(&self.search_query)
                             && !filtered_cards.contains(&item)
                     })
                     .collect();
                 filtered_cards.append(&mut filtered_cards_type);
-                let mut filtered_cards_desc: Vec<_> = SEARCH_ITEMS
-                    .iter()
+                let mut filtered_cards_desc: Vec<_> = search_items()
                     .filter(|item| {
                         item.desc.to_lowercase().contains(&self.search_query)
                             && !filtered_cards.contains(&item)
@@ -1,3 +1,4 @@
 pub mod card;
 pub mod docs;
 pub mod menu;
+pub mod reference;
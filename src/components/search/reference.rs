@@ -0,0 +1,125 @@
+use super::docs::{CommandType, SearchItemData, SEARCH_ITEMS};
+use crate::parser::utils::opcodes::{
+    dynamic_gas, upgrades, Fork, Opcode, Precompile, OPCODES, PRECOMPILES,
+};
+
+lazy_static::lazy_static! {
+    /// The opcode and precompile reference, built from the tables of the disassembler and the
+    /// EVM executor so that they can't drift apart.
+    pub static ref REFERENCE_ITEMS: Vec<SearchItemData> = OPCODES
+        .iter()
+        .map(opcode_item)
+        .chain(PRECOMPILES.iter().map(precompile_item))
+        .enumerate()
+        .map(|(i, item)| SearchItemData {
+            id: SEARCH_ITEMS.len() + i,
+            ..item
+        })
+        .collect();
+}
+
+/// The builtins, followed by the opcode and precompile reference.
+pub fn search_items() -> impl Iterator<Item = &'static SearchItemData> {
+    SEARCH_ITEMS.iter().chain(REFERENCE_ITEMS.iter())
+}
+
+/// The items are built once and live as long as the app, like the builtins docs.
+fn leak(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
+
+fn eip_link(eip: u16) -> String {
+    format!("https://eips.ethereum.org/EIPS/eip-{}", eip)
+}
+
+fn opcode_item(op: &'static Opcode) -> SearchItemData {
+    let upgrades: Vec<_> = upgrades(op.code).collect();
+    let gas = match dynamic_gas(op.code) {
+        Some(dynamic) => format!("{} {}", op.gas, dynamic),
+        None => op.gas.to_string(),
+    };
+    let introduced = upgrades.iter().find(|upgrade| upgrade.introduced);
+
+    // gas per hardfork, starting with the Frontier cost of the instructions it didn't list
+    let mut history = Vec::new();
+    if introduced.is_none() && upgrades.first().is_none_or(|u| u.fork > Fork::Frontier) {
+        history.push(format!("// {:<18} {}", Fork::Frontier.name(), op.gas));
+    }
+    for upgrade in &upgrades {
+        let eip = upgrade.eip.map(|eip| format!("  EIP-{}", eip));
+        history.push(format!(
+            "// {:<18} {}{}",
+            upgrade.fork.name(),
+            upgrade.gas,
+            eip.unwrap_or_default()
+        ));
+    }
+
+    let mut desc = vec![
+        format!(
+            "Stack: `{}` in, `{}` out. Gas (Cancun): `{}`.",
+            op.inputs, op.outputs, gas
+        ),
+        match introduced {
+            Some(upgrade) => format!(
+                "Introduced in {} by EIP-{}.",
+                upgrade.fork.name(),
+                upgrade.eip.unwrap_or_default()
+            ),
+            None => format!("Introduced in {}.", Fork::Frontier.name()),
+        },
+    ];
+    let mut eips: Vec<u16> = upgrades.iter().filter_map(|upgrade| upgrade.eip).collect();
+    eips.dedup();
+    desc.extend(eips.into_iter().map(eip_link));
+
+    SearchItemData {
+        id: 0,
+        command: op.name,
+        c_type: CommandType::Opcode,
+        alias: Some(leak(format!("0x{:02x}", op.code))),
+        params: Some(leak(format!(
+            "{} `0x{:02x}`\u{00a0}\u{00a0}stack: {} → {}",
+            op.name, op.code, op.inputs, op.outputs
+        ))),
+        example: Some(leak(history.join("\n"))),
+        desc: leak(desc.join("\n")),
+    }
+}
+
+fn precompile_item(precompile: &'static Precompile) -> SearchItemData {
+    let mut history = vec![format!(
+        "// {:<18} introduced{}",
+        precompile.fork.name(),
+        precompile
+            .eip
+            .map(|eip| format!("  EIP-{}", eip))
+            .unwrap_or_default()
+    )];
+    for (fork, eip, gas) in precompile.repricings {
+        history.push(format!("// {:<18} {}  EIP-{}", fork.name(), gas, eip));
+    }
+
+    let mut desc = vec![format!(
+        "Precompiled contract at `0x{:02x}`. Gas (Cancun): `{}`.",
+        precompile.address, precompile.gas
+    )];
+    let eips = precompile
+        .eip
+        .into_iter()
+        .chain(precompile.repricings.iter().map(|(_, eip, _)| *eip));
+    desc.extend(eips.map(eip_link));
+
+    SearchItemData {
+        id: 0,
+        command: precompile.name,
+        c_type: CommandType::Precompile,
+        alias: Some(leak(format!("0x{:02x}", precompile.address))),
+        params: Some(leak(format!(
+            "{} `0x{:040x}`",
+            precompile.name, precompile.address
+        ))),
+        example: Some(leak(history.join("\n"))),
+        desc: leak(desc.join("\n")),
+    }
+}
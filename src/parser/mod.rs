@@ -7,13 +7,14 @@ mod macros;
 pub mod types;
 pub mod utils;
 use crate::components::playground::types::BlockState;
-use convert_chart::{convert, UnitType};
+use convert_chart::{convert, EvmGas, UnitType};
 use types::{abi::*, result::*};
 use utils::{uniswap_v3::*, *};
 
 use alloy_core::primitives::{
-    utils::{format_ether, format_units, keccak256},
-    B256, U256,
+    hex,
+    utils::{eip191_hash_message, format_ether, format_units, keccak256},
+    Address, B256, U256,
 };
 use base64::prelude::*;
 use chrono::Utc;
@@ -44,12 +45,178 @@ lazy_static::lazy_static! {
     };
 }
 
+/// Functions whose hex and address literal arguments are taken as written, ie: the casing of
+/// the address passed to `is_checksummed` or the leading zero bytes of `rlp_encode(0x0001)` and
+/// `disassemble(0x00602a)`.
+const LITERAL_FNS: &[&str] = &[
+    "is_checksummed",
+    "is_checksum",
+    "rlp_encode",
+    "rlp_decode",
+    "eip191_hash",
+    "hash_message",
+    "safe_tx_hash",
+    "decode",
+    "decode_calldata",
+    "abi_decode",
+    "guess_selector",
+    "fn_from_selector",
+    "guess_error",
+    "error_from_selector",
+    "decode_revert",
+    "decode_error",
+    "metadata",
+    "decode_metadata",
+    "constructor_args",
+    "multicall_decode",
+    "inspect_calldata",
+    "inspect",
+    "multicall_encode",
+    "disassemble",
+    "disasm",
+    "modexp",
+    "blake2f",
+    "sha256",
+    "ripemd160",
+    "identity",
+];
+/// Named arguments taking raw bytes, whose hex literals are taken as written as well.
+const LITERAL_ARGS: &[&str] = &["data", "input", "calldata", "code", "bytecode"];
+
 pub fn parse(input: &str, blocks: &Vec<BlockState>) -> ParseResult {
-    let parse_result = Calculator::parse(Rule::calculation, input);
+    let line = input.replace("\n", "");
+    let parse_result = Calculator::parse(Rule::calculation, &line);
     match parse_result {
         Ok(r) => eval(r, false, blocks),
-        Err(_) => ParseResult::NAN,
+        // pasted json payloads, ie: `eth_signTypedData_v4` typed data
+        Err(_) => match serde_json::from_str::<serde_json::Value>(&line) {
+            Ok(json) if json.is_object() || json.is_array() => json.into(),
+            // pasted Solidity interfaces and human-readable ABIs, which need their line breaks
+            _ => match interface::parse_interface(input) {
+                Some(Ok(abi)) => abi.into(),
+                Some(Err(e)) => {
+                    log!(format!("Invalid interface: {}", e));
+                    ParseResult::NAN
+                }
+                None => ParseResult::NAN,
+            },
+        },
+    }
+}
+
+/// Finds the private keys passed to key-consuming functions (ie: `sign(hash, key)` or
+/// `tx(.., key=..)`). Returns whether any key is written in the input itself (rather than
+/// being a plain block reference), and the labels of the blocks referenced by the keys.
+pub fn key_args(input: &str) -> (bool, Vec<String>) {
+    let mut literal = false;
+    let mut labels = Vec::new();
+    let input = input.replace("\n", "");
+    let pairs = match Calculator::parse(Rule::calculation, &input) {
+        Ok(pairs) => pairs,
+        Err(_) => return (literal, labels),
+    };
+    for pair in pairs.flatten().filter(|p| p.as_rule() == Rule::function) {
+        let mut inner = pair.into_inner();
+        let func = inner.next().unwrap().as_str();
+        let key_pos = signature::KEY_FNS
+            .iter()
+            .find(|(f, _)| *f == func)
+            .map(|(_, pos)| *pos);
+        for (i, arg) in inner.enumerate() {
+            let key = match arg.as_rule() {
+                Rule::named_arg => {
+                    let mut named = arg.into_inner();
+                    match named.next() {
+                        Some(name) if signature::KEY_ARGS.contains(&name.as_str()) => named.next(),
+                        _ => None,
+                    }
+                }
+                _ if Some(i) == key_pos => Some(arg),
+                _ => None,
+            };
+            if let Some(key) = key {
+                let refs = referenced_labels(key.as_str());
+                if refs.len() != 1 || refs[0] != key.as_str().trim().to_lowercase() {
+                    literal = true;
+                }
+                labels.extend(refs);
+            }
+        }
     }
+    (literal, labels)
+}
+
+/// Returns the canonical form of the signatures passed to `selector`, which is what gets hashed.
+pub fn hashed_signatures(input: &str, blocks: &Vec<BlockState>) -> Vec<String> {
+    let input = input.replace("\n", "");
+    let pairs = match Calculator::parse(Rule::calculation, &input) {
+        Ok(pairs) => pairs,
+        Err(_) => return Vec::new(),
+    };
+    pairs
+        .flatten()
+        .filter(|p| p.as_rule() == Rule::function)
+        .filter_map(|pair| {
+            let mut inner = pair.into_inner();
+            if inner.next()?.as_str() != "selector" {
+                return None;
+            }
+            let arg = inner.next()?;
+            let sig = match arg.as_rule() {
+                Rule::quote => trim_quotes(arg.as_str()),
+                _ => eval(arg.into_inner(), false, blocks).get_string()?,
+            };
+            selectors::canonical_signature(&sig).ok()
+        })
+        .collect()
+}
+
+/// Returns the (lowercased) identifiers used in the input, which may reference other blocks.
+pub fn referenced_labels(input: &str) -> Vec<String> {
+    let input = input.replace("\n", "");
+    let pairs = match Calculator::parse(Rule::calculation, &input) {
+        Ok(pairs) => pairs,
+        Err(_) => return Vec::new(),
+    };
+    // function, argument and ABI item names are idents as well
+    let names = pairs
+        .clone()
+        .flatten()
+        .filter_map(|p| match p.as_rule() {
+            Rule::function | Rule::named_arg => p.into_inner().next(),
+            Rule::member => p.into_inner().nth(1),
+            _ => None,
+        })
+        .map(|p| p.as_span())
+        .collect::<Vec<_>>();
+    pairs
+        .flatten()
+        .filter(|p| p.as_rule() == Rule::ident && !names.contains(&p.as_span()))
+        .map(|p| p.as_str().trim().to_lowercase())
+        .collect()
+}
+
+/// Evaluates an argument, keeping a hex or address literal (possibly in an array) as its
+/// source text rather than as a number or a parsed address.
+fn eval_literal(pair: Pair<Rule>, unchecked: bool, blocks: &Vec<BlockState>) -> ParseResult {
+    let mut inner = pair.clone().into_inner();
+    if let (Some(atom), None) = (inner.next(), inner.next()) {
+        match atom.as_rule() {
+            Rule::hex | Rule::address => return atom.as_str().trim().to_string().into(),
+            Rule::array | Rule::tuple => {
+                return atom
+                    .into_inner()
+                    .map(|pair| match pair.as_rule() {
+                        Rule::quote => trim_quotes(pair.as_str()).into(),
+                        _ => eval_literal(pair, unchecked, blocks),
+                    })
+                    .collect::<Vec<ParseResult>>()
+                    .into()
+            }
+            _ => {}
+        }
+    }
+    eval(pair.into_inner(), unchecked, blocks)
 }
 
 fn eval(expression: Pairs<Rule>, unchecked: bool, blocks: &Vec<BlockState>) -> ParseResult {
@@ -96,16 +263,49 @@ fn eval(expression: Pairs<Rule>, unchecked: bool, blocks: &Vec<BlockState>) -> P
                     ParseResult::NAN
                 }
             }
+            Rule::amount => {
+                let mut i = pair.into_inner();
+                let value = i.next().unwrap().as_str();
+                let unit = i.next().unwrap().into_inner().next().unwrap().as_rule();
+                match format!("EVMGAS::{:?}", unit).parse::<UnitType>() {
+                    Ok(from) => convert(value, from, UnitType::EVMGAS(EvmGas::WEI)).into(),
+                    Err(_) => ParseResult::NAN,
+                }
+            }
             Rule::function => {
                 let mut pairs = pair.into_inner();
                 let func = pairs.next().unwrap().as_str();
                 let unchecked = if func == "unchecked" { true } else { unchecked };
-                let args = pairs
-                    .map(|pair| match pair.as_rule() {
-                        Rule::quote => trim_quotes(pair.as_str()).into(),
-                        _ => eval(pair.into_inner(), unchecked, blocks),
-                    })
-                    .collect::<Vec<ParseResult>>();
+                let mut args = Vec::new();
+                let mut named_args = Vec::new();
+                for pair in pairs {
+                    match pair.as_rule() {
+                        Rule::quote => args.push(trim_quotes(pair.as_str()).into()),
+                        Rule::named_arg => {
+                            let mut inner = pair.into_inner();
+                            let name = inner.next().unwrap().as_str().to_string();
+                            let value = inner.next().unwrap();
+                            let value = match value.as_rule() {
+                                Rule::quote => trim_quotes(value.as_str()).into(),
+                                _ if LITERAL_ARGS.contains(&name.as_str()) => {
+                                    eval_literal(value, unchecked, blocks)
+                                }
+                                _ => eval(value.into_inner(), unchecked, blocks),
+                            };
+                            named_args.push((name, value));
+                        }
+                        _ if LITERAL_FNS.contains(&func) => {
+                            args.push(eval_literal(pair, unchecked, blocks))
+                        }
+                        _ => args.push(eval(pair.into_inner(), unchecked, blocks)),
+                    }
+                }
+                if !named_args.is_empty() {
+                    return named_fn_args(func, args, named_args);
+                }
+                if let Some(result) = notebook_fn_args(func, &args, blocks) {
+                    return result;
+                }
                 utility_fn_args(func, args)
             }
             Rule::now => U256::from(Utc::now().timestamp()).into(),
@@ -132,6 +332,18 @@ fn eval(expression: Pairs<Rule>, unchecked: bool, blocks: &Vec<BlockState>) -> P
                     value_str.parse::<U256>().ok().into()
                 }
             }
+            Rule::address => {
+                let addr = pair.as_str().trim();
+                // mixed-case literals carry an EIP-55 checksum, which must be valid
+                let has_checksum = addr[2..].chars().any(|c| c.is_ascii_lowercase())
+                    && addr[2..].chars().any(|c| c.is_ascii_uppercase());
+                let parsed = if has_checksum {
+                    Address::parse_checksummed(addr, None).map_err(|e| e.to_string())
+                } else {
+                    addr.parse::<Address>().map_err(|e| e.to_string())
+                };
+                unwrap_or_nan!(parsed, "Invalid address checksum").into()
+            }
             Rule::hex => {
                 let pref_hex = pair.as_str().trim();
                 let hex = if pref_hex.starts_with("0x") {
@@ -139,7 +351,11 @@ fn eval(expression: Pairs<Rule>, unchecked: bool, blocks: &Vec<BlockState>) -> P
                 } else {
                     &pref_hex[1..]
                 };
-                U256::from_str_radix(hex, 16).ok().into()
+                match U256::from_str_radix(hex, 16) {
+                    Ok(value) => value.into(),
+                    // keep longer byte strings (ie: signatures, public keys) as hex
+                    Err(_) => pref_hex.to_string().into(),
+                }
             }
             Rule::bin => {
                 let pref_bin = pair.as_str().trim();
@@ -151,6 +367,14 @@ fn eval(expression: Pairs<Rule>, unchecked: bool, blocks: &Vec<BlockState>) -> P
                 U256::from_str_radix(bin, 2).ok().into()
             }
             Rule::quote => trim_quotes(pair.as_str()).into(),
+            Rule::array | Rule::tuple => pair
+                .into_inner()
+                .map(|pair| match pair.as_rule() {
+                    Rule::quote => trim_quotes(pair.as_str()).into(),
+                    _ => eval(pair.into_inner(), unchecked, blocks),
+                })
+                .collect::<Vec<ParseResult>>()
+                .into(),
             Rule::expr => eval(pair.into_inner(), unchecked, blocks),
             Rule::ident => {
                 let id = pair.as_str().trim();
@@ -159,16 +383,42 @@ fn eval(expression: Pairs<Rule>, unchecked: bool, blocks: &Vec<BlockState>) -> P
                 } else if id == "false" {
                     U256::from(0).into()
                 } else {
-                    match blocks.iter().find(|b| b.get_id() == id.to_lowercase()) {
-                        Some(block) => block.get_result().into(),
-                        None => ParseResult::NAN,
-                    }
+                    find_block(id, blocks).unwrap_or(ParseResult::NAN)
                 }
             }
+            Rule::member => {
+                let mut inner = pair.into_inner();
+                let (name, item) = (inner.next().unwrap(), inner.next().unwrap());
+                let signature = named_abi(name.as_str(), blocks)
+                    .and_then(|abi| interface::item_signature(&abi, item.as_str()));
+                // otherwise a field of the block, ie: the bytecode of an imported artifact
+                let field = find_block(name.as_str(), blocks)
+                    .and_then(|block| block.get_json()?.get(item.as_str()).cloned());
+                let result = signature.map(ParseResult::from).or_else(|e| match field {
+                    Some(serde_json::Value::String(s)) => Ok(s.into()),
+                    Some(field) => Ok(field.into()),
+                    None => Err(e),
+                });
+                unwrap_or_nan!(result, "Invalid ABI item")
+            }
+            Rule::method => {
+                let mut inner = pair.into_inner();
+                let mut member = inner.next().unwrap().into_inner();
+                let (name, func) = (member.next().unwrap(), member.next().unwrap());
+                let args = inner
+                    .map(|pair| match pair.as_rule() {
+                        Rule::quote => trim_quotes(pair.as_str()).into(),
+                        _ => eval(pair.into_inner(), unchecked, blocks),
+                    })
+                    .collect::<Vec<ParseResult>>();
+                let calldata = named_abi(name.as_str(), blocks)
+                    .and_then(|abi| interface::encode_call(&abi, func.as_str(), &args));
+                unwrap_or_nan!(calldata, "Invalid call").into()
+            }
             _ => ParseResult::NAN,
         },
         |lhs: ParseResult, op: Pair<Rule>, rhs: ParseResult| {
-            let (lhs, rhs) = match (lhs, rhs) {
+            let (lhs, rhs) = match (lhs.address_to_value(), rhs.address_to_value()) {
                 (ParseResult::Value(lhs), ParseResult::Value(rhs)) => (lhs, rhs),
                 _ => return ParseResult::NAN,
             };
@@ -339,12 +589,32 @@ fn utility_fn_args(func: &str, args: Vec<ParseResult>) -> ParseResult {
             .map(|arg| match arg {
                 ParseResult::String(s) => s.to_string(),
                 ParseResult::Value(u) => u.to_string(),
+                ParseResult::Address(a) => a.to_checksum(None),
                 _ => "".to_string(),
             })
             .collect::<Vec<String>>()
             .join(", ")
             .into();
     }
+    if let Some(result) = address_fn_args(func, &args) {
+        return result;
+    }
+    if let Some(result) = signature_fn_args(func, &args) {
+        return result;
+    }
+    if let Some(result) = eip712_fn_args(func, &args) {
+        return result;
+    }
+    if let Some(result) = abi_fn_args(func, &args) {
+        return result;
+    }
+    if let Some(result) = encode_fn_args(func, &args) {
+        return result;
+    }
+    if let Some(result) = precompile_fn_args(func, &args) {
+        return result;
+    }
+    let args: Vec<ParseResult> = args.into_iter().map(|a| a.address_to_value()).collect();
     match args.len() {
         1 => match &args[0] {
             ParseResult::String(arg0) => match func {
@@ -353,14 +623,35 @@ fn utility_fn_args(func: &str, args: Vec<ParseResult>) -> ParseResult {
                     Some(arg0) => arg0.parse::<B256>().unwrap_or_default().to_string().into(),
                     None => ParseResult::NAN,
                 },
-                "address" | "addr" | "checksum" => {
-                    let u = arg0.parse::<U256>().unwrap_or_default();
-                    u256_to_address(u).to_string().into()
+                // hex strings (ie: encoded values) are hashed as bytes
+                "keccak256" | "sha3" | "hash" => match arg0.strip_prefix("0x").map(hex::decode) {
+                    Some(Ok(bytes)) => keccak256(bytes).to_string().into(),
+                    _ => keccak256(arg0).to_string().into(),
+                },
+                "eip191_hash" | "hash_message" => {
+                    let message = match arg0.strip_prefix("0x").map(hex::decode) {
+                        Some(Ok(bytes)) => bytes,
+                        _ => arg0.as_bytes().to_vec(),
+                    };
+                    eip191_hash_message(message).to_string().into()
                 }
-                "keccak256" | "sha3" | "hash" => keccak256(arg0).to_string().into(),
-                "selector" => keccak256(arg0.replace(' ', "")).to_string()[..10]
+                "namehash" => unwrap_or_nan!(ens::namehash(arg0), "Invalid ENS name")
                     .to_string()
                     .into(),
+                "labelhash" => unwrap_or_nan!(ens::labelhash(arg0), "Invalid ENS label")
+                    .to_string()
+                    .into(),
+                "selector" => format!(
+                    "0x{}",
+                    hex::encode(unwrap_or_nan!(
+                        selectors::selector(arg0),
+                        "Invalid signature"
+                    ))
+                )
+                .into(),
+                "canonical_signature" | "canonical_sig" => {
+                    unwrap_or_nan!(selectors::canonical_signature(arg0), "Invalid signature").into()
+                }
                 "debug" => {
                     let (prefix, start) = if arg0.starts_with("0x") {
                         match arg0.len() % 64 {
@@ -386,7 +677,17 @@ fn utility_fn_args(func: &str, args: Vec<ParseResult>) -> ParseResult {
                     }
                     formatted.into()
                 }
-                "guess_selector" | "fn_from_selector" => "to do".into(),
+                "rlp_encode" => format!(
+                    "0x{}",
+                    hex::encode(unwrap_or_nan!(rlp::rlp_encode(&args[0])))
+                )
+                .into(),
+                "rlp_decode" => unwrap_or_nan!(rlp::rlp_decode(arg0), "Invalid RLP")
+                    .to_json()
+                    .into(),
+                "decode_tx" => {
+                    unwrap_or_nan!(tx::decode_tx(arg0, None), "Invalid transaction").into()
+                }
                 // string manipulation
                 "len" | "chars" => U256::from(arg0.len()).into(),
                 "lowercase" | "lower" => arg0.to_lowercase().into(),
@@ -429,8 +730,32 @@ fn utility_fn_args(func: &str, args: Vec<ParseResult>) -> ParseResult {
             ParseResult::Value(arg0) => match func {
                 // evm utils
                 "bytes32" => B256::from(*arg0).to_string().into(),
-                "address" | "addr" | "checksum" => u256_to_address(*arg0).to_string().into(),
+                "unpack_gas_limits" => {
+                    let (verification, call) = user_op::unpack_uint128(*arg0);
+                    serde_json::json!({
+                        "verificationGasLimit": verification.to_string(),
+                        "callGasLimit": call.to_string(),
+                    })
+                    .into()
+                }
+                "unpack_gas_fees" => {
+                    let (priority_fee, max_fee) = user_op::unpack_uint128(*arg0);
+                    serde_json::json!({
+                        "maxPriorityFeePerGas": priority_fee.to_string(),
+                        "maxFeePerGas": max_fee.to_string(),
+                    })
+                    .into()
+                }
+                // computed numbers are signed as 32-byte words, ie: a message hash
+                "eip191_hash" | "hash_message" => {
+                    eip191_hash_message(B256::from(*arg0)).to_string().into()
+                }
                 "sqrt" => arg0.root(2).into(),
+                "rlp_encode" => format!(
+                    "0x{}",
+                    hex::encode(unwrap_or_nan!(rlp::rlp_encode(&args[0])))
+                )
+                .into(),
                 // uniswap v3 utils
                 x if is_command!(x, GET_TICK) => unwrap_or_nan!(
                     uniswap_v3_math::tick_math::get_tick_at_sqrt_ratio(*arg0),
@@ -463,6 +788,17 @@ fn utility_fn_args(func: &str, args: Vec<ParseResult>) -> ParseResult {
                 "unix" => format_unix(*arg0, None),
                 _ => ParseResult::NAN,
             },
+            ParseResult::Json(arg0) if func == "eip712_hash" => {
+                unwrap_or_nan!(eip712::eip712_hash(arg0), "Invalid typed data").into()
+            }
+            ParseResult::Array(_) | ParseResult::Json(_) => match func {
+                "rlp_encode" => format!(
+                    "0x{}",
+                    hex::encode(unwrap_or_nan!(rlp::rlp_encode(&args[0])))
+                )
+                .into(),
+                _ => ParseResult::NAN,
+            },
             _ => ParseResult::NAN,
         },
         2 => match (&args[0], &args[1]) {
@@ -486,39 +822,12 @@ fn utility_fn_args(func: &str, args: Vec<ParseResult>) -> ParseResult {
                         ParseResult::Value(token_b).to_hex_string(false).into()
                     }
                 }
-                "abi_encode" => {
-                    let args = split_top_level(trim_parentheses(&arg1));
-                    unwrap_or_nan!(abi_encode(&arg0, args, false)).into()
-                }
-                "abi_encode_with_sig" | "abi_encode_with_selector" => {
-                    let args = arg1
-                        .split(",")
-                        .into_iter()
-                        .map(|s| s.trim().to_owned())
-                        .collect();
-                    unwrap_or_nan!(abi_encode(&arg0, args, true)).into()
-                }
-                "abi_decode" => match abi_process_and_decode_calldata(&arg0, &arg1) {
-                    (Some(selector), Ok(decoded)) => {
-                        match serde_json::to_value(&decoded) {
-                            Ok(mut json) => {
-                                // Convert serde_json::Value into Vec<serde_json::Value>
-                                if let Some(array) = json.as_array_mut() {
-                                    array.insert(0, serde_json::to_value(&selector).unwrap());
-                                }
-                                // Convert Vec<serde_json::Value> back into serde_json::Value
-                                let json =
-                                    serde_json::Value::Array(json.as_array().unwrap().clone());
-                                json.into()
-                            }
-                            Err(_) => ParseResult::NAN,
-                        }
-                    }
-                    (None, Ok(decoded)) => match serde_json::to_value(&decoded) {
-                        Ok(json) => json.into(),
-                        Err(_) => ParseResult::NAN,
-                    },
-                    (_, Err(_)) => ParseResult::NAN,
+                "decode_tx" => {
+                    unwrap_or_nan!(tx::decode_tx(arg0, Some(arg1)), "Invalid transaction").into()
+                }
+                "abi_decode" => match abi_decode_to_json(arg0, arg1) {
+                    Ok(json) => json.into(),
+                    Err(_) => ParseResult::NAN,
                 },
                 _ => ParseResult::NAN,
             },
@@ -542,6 +851,11 @@ fn utility_fn_args(func: &str, args: Vec<ParseResult>) -> ParseResult {
                 _ => ParseResult::NAN,
             },
             (ParseResult::Value(arg0), ParseResult::Value(arg1)) => match func {
+                "pack_gas_limits" | "pack_gas_fees" | "pack_uint128" => {
+                    unwrap_or_nan!(user_op::pack_uint128(*arg0, *arg1))
+                        .to_string()
+                        .into()
+                }
                 "root" => arg0.root(arg1.to_string().parse().unwrap_or(2)).into(),
                 "format_units" => format_units(*arg0, arg1.to_string()).ok().into(),
                 x if is_command!(x, GET_TOKEN0) => {
@@ -574,6 +888,9 @@ fn utility_fn_args(func: &str, args: Vec<ParseResult>) -> ParseResult {
             (ParseResult::Value(arg0), ParseResult::Value(arg1), ParseResult::Value(arg2)) => {
                 match func {
                     "unix" => build_unix(vec![arg0, arg1, arg2]).into(),
+                    "auth_hash" => tx::auth_hash(*arg0, u256_to_address(*arg1), *arg2)
+                        .to_string()
+                        .into(),
                     "get_lower_tick" => match get_lower_tick(*arg0, *arg1, *arg2) {
                         Some(tick) => tick.to_string().into(),
                         None => ParseResult::NAN,
@@ -610,6 +927,16 @@ fn utility_fn_args(func: &str, args: Vec<ParseResult>) -> ParseResult {
                     _ => ParseResult::NAN,
                 }
             }
+            (ParseResult::Json(arg0), ParseResult::Value(arg1), ParseResult::Value(arg2)) => {
+                match func {
+                    "user_op_hash" => unwrap_or_nan!(
+                        user_op::user_op_hash(arg0, u256_to_address(*arg1), *arg2),
+                        "Invalid user operation"
+                    )
+                    .into(),
+                    _ => ParseResult::NAN,
+                }
+            }
             (ParseResult::String(arg0), ParseResult::Value(arg1), ParseResult::Value(arg2)) => {
                 match func {
                     x if is_command!(x, GET_PRICE) => {
@@ -697,3 +1024,430 @@ fn utility_fn_args(func: &str, args: Vec<ParseResult>) -> ParseResult {
         _ => ParseResult::NAN,
     }
 }
+
+/// Handles the functions called with named arguments, ie: `tx(to=0x.., value=1 ether)`.
+fn named_fn_args(
+    func: &str,
+    args: Vec<ParseResult>,
+    named_args: Vec<(String, ParseResult)>,
+) -> ParseResult {
+    if !args.is_empty() {
+        return ParseResult::NAN;
+    }
+    match func {
+        "tx" | "transaction" => {
+            unwrap_or_nan!(tx::build_tx(&named_args), "Invalid transaction").into()
+        }
+        "evm" | "execute" => {
+            unwrap_or_nan!(executor::execute(&named_args), "Invalid execution").into()
+        }
+        _ => ParseResult::NAN,
+    }
+}
+
+/// Handles the secp256k1 signing, recovery and signature format helpers.
+fn signature_fn_args(func: &str, args: &Vec<ParseResult>) -> Option<ParseResult> {
+    let result = match (func, args.as_slice()) {
+        ("sign", [hash, key]) => signature::to_b256(hash)
+            .and_then(|hash| signature::sign(hash, &signature::signing_key(key)?))
+            .map(|sig| format!("0x{}", hex::encode(sig.to_bytes())).into()),
+        ("ecrecover", [hash, sig]) => signature::to_b256(hash)
+            .and_then(|hash| signature::Sig::parse(sig)?.recover(hash))
+            .map(ParseResult::from),
+        ("ecrecover", [hash, v, r, s]) => signature::to_b256(hash)
+            .and_then(|hash| signature::Sig::from_vrs(v, r, s)?.recover(hash))
+            .map(ParseResult::from),
+        ("pubkey" | "public_key", [key]) => signature::signing_key(key)
+            .map(|key| format!("0x{}", hex::encode(signature::pubkey(&key))).into()),
+        ("address_of", [key]) => signature::address_of(key).map(ParseResult::from),
+        ("split_sig" | "split_signature", [sig]) => {
+            signature::Sig::parse(sig).map(|sig| sig.to_json().into())
+        }
+        ("compact_sig" | "to_eip2098", [sig]) => signature::Sig::parse(sig)
+            .and_then(|sig| sig.to_compact())
+            .map(|sig| format!("0x{}", hex::encode(sig)).into()),
+        ("normalize_sig" | "normalize_s", [sig]) => signature::Sig::parse(sig)
+            .map(|sig| format!("0x{}", hex::encode(sig.normalize_s().to_bytes())).into()),
+        _ => return None,
+    };
+    match result {
+        Ok(result) => Some(result),
+        Err(e) => {
+            log!(format!("Invalid signature input: {}", e));
+            Some(ParseResult::NAN)
+        }
+    }
+}
+
+/// Handles the EIP-712 digest presets, which take more arguments than the generic dispatch.
+fn eip712_fn_args(func: &str, args: &[ParseResult]) -> Option<ParseResult> {
+    let result = match func {
+        "erc2612_permit_digest" | "permit_digest" => eip712::erc2612_permit_digest(args),
+        "permit2_single_digest" | "permit_single_digest" => eip712::permit2_single_digest(args),
+        "permit2_transfer_digest" | "permit_transfer_from_digest" => {
+            eip712::permit2_transfer_digest(args)
+        }
+        "safe_tx_hash" => eip712::safe_tx_hash(args),
+        _ => return None,
+    };
+    match result {
+        Ok(result) => Some(result.into()),
+        Err(e) => {
+            log!(format!("Invalid typed data: {}", e));
+            Some(ParseResult::NAN)
+        }
+    }
+}
+
+/// Extracts the revert data of a pasted JSON-RPC error (`{"code": 3, "message": .., "data": ..}`).
+fn revert_data(data: &ParseResult) -> ParseResult {
+    let json = match data {
+        ParseResult::Json(json) => json.get("error").unwrap_or(json),
+        _ => return data.clone(),
+    };
+    match json.get("data") {
+        Some(serde_json::Value::String(data)) => data.to_string().into(),
+        _ => ParseResult::NAN,
+    }
+}
+
+/// Finds a block by its label or, for pasted Solidity interfaces and imported artifacts, by the
+/// interface or contract name.
+fn find_block(id: &str, blocks: &[BlockState]) -> Option<ParseResult> {
+    let id = id.to_lowercase();
+    blocks
+        .iter()
+        .find(|b| b.get_id() == id)
+        .or_else(|| {
+            blocks.iter().find(|b| match b.get_result() {
+                ParseResult::Json(json) => ["interface", "contract"].iter().any(|key| {
+                    json[key]
+                        .as_str()
+                        .is_some_and(|name| name.to_lowercase() == id)
+                }),
+                _ => false,
+            })
+        })
+        .map(|b| b.get_result())
+}
+
+fn named_abi(name: &str, blocks: &[BlockState]) -> Result<alloy_json_abi::JsonAbi, String> {
+    let abi = find_block(name, blocks).ok_or(format!("no block named {}", name))?;
+    parse_json_abi(&abi)
+}
+
+/// Extracts the "result" of a pasted JSON-RPC response (ie: of an `eth_call`).
+fn rpc_result(data: &ParseResult) -> ParseResult {
+    match data {
+        ParseResult::Json(json) => match json.get("result") {
+            Some(serde_json::Value::String(result)) => result.to_string().into(),
+            _ => ParseResult::NAN,
+        },
+        _ => data.clone(),
+    }
+}
+
+/// Handles the functions that look through the other blocks of the notebook, ie: for ABIs.
+fn notebook_fn_args(
+    func: &str,
+    args: &Vec<ParseResult>,
+    blocks: &[BlockState],
+) -> Option<ParseResult> {
+    match (func, args.as_slice()) {
+        ("decode_log" | "decode_logs", [ParseResult::Json(receipt)]) => {
+            match logs::decode_receipt_logs(receipt, &notebook_abis(blocks)) {
+                Ok(result) => Some(result.into()),
+                Err(e) => {
+                    log!(format!("Invalid receipt: {}", e));
+                    Some(ParseResult::NAN)
+                }
+            }
+        }
+        ("disassemble" | "disasm", [code]) => match code.get_bytes() {
+            Ok(code) => Some(disassembler::disassemble(&code, &notebook_abis(blocks)).into()),
+            Err(e) => {
+                log!(format!("Invalid bytecode: {}", e));
+                Some(ParseResult::NAN)
+            }
+        },
+        _ => None,
+    }
+}
+
+/// The ABIs of the notebook, ie: pasted JSON ABIs, interfaces and imported artifacts.
+fn notebook_abis(blocks: &[BlockState]) -> Vec<alloy_json_abi::JsonAbi> {
+    blocks
+        .iter()
+        .filter_map(|block| match block.get_result() {
+            abi @ (ParseResult::Json(_) | ParseResult::Array(_)) => parse_json_abi(&abi).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Handles the ABI encoding functions, which take a signature (or a list of types) followed
+/// by the values, either as they are or listed in a single string, and the multicall batches.
+fn encode_fn_args(func: &str, args: &Vec<ParseResult>) -> Option<ParseResult> {
+    let result = match (func, args.as_slice()) {
+        ("multicall_encode", [calls]) => multicall::encode_aggregate3(calls),
+        ("multicall_encode", [require_success, calls]) => {
+            multicall::encode_try_aggregate(require_success, calls)
+        }
+        (_, [ParseResult::String(sig), values @ ..]) if !values.is_empty() => match func {
+            "abi_encode" => abi_encode_values(sig, values, false),
+            "abi_encode_with_sig" | "abi_encode_with_selector" => {
+                abi_encode_values(sig, values, true)
+            }
+            "abi_encode_packed" | "encode_packed" => abi_encode_packed(sig, values),
+            "abi_encode_params" => abi_encode_params(sig, values),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match result {
+        Ok(result) => Some(result.into()),
+        Err(e) => {
+            log!(format!("Invalid ABI encoding input: {}", e));
+            Some(ParseResult::NAN)
+        }
+    }
+}
+
+/// Lists the candidate signatures of a selector lookup.
+fn lookup(candidates: impl Iterator<Item = String>) -> serde_json::Value {
+    signatures::candidates(candidates).into()
+}
+
+/// Handles the functions that take a JSON or human-readable ABI block.
+fn abi_fn_args(func: &str, args: &Vec<ParseResult>) -> Option<ParseResult> {
+    let result = match (func, args.as_slice()) {
+        ("decode" | "decode_calldata", [abi, calldata]) => {
+            parse_json_abi(abi).and_then(|abi| decode_calldata(&abi, &calldata.get_bytes()?))
+        }
+        ("decode_log", [abi, topics, data]) => logs::decode_log(abi, topics, data),
+        ("abi_decode" | "decode", [calldata]) => calldata.get_bytes().and_then(|data| {
+            decode_calldata_candidates(signatures::functions(data.get(..4).unwrap_or(&[])), &data)
+        }),
+        ("guess_selector" | "fn_from_selector", [selector]) => {
+            selector.get_bytes().map(|selector| {
+                lookup(
+                    signatures::functions(&selector)
+                        .iter()
+                        .map(|f| f.signature()),
+                )
+            })
+        }
+        ("guess_error" | "error_from_selector", [selector]) => selector
+            .get_bytes()
+            .map(|selector| lookup(signatures::errors(&selector).iter().map(|e| e.signature()))),
+        ("guess_event" | "event_from_topic", [topic]) => signature::to_b256(topic).map(|topic| {
+            lookup(
+                signatures::events(topic.as_slice())
+                    .iter()
+                    .map(|e| e.signature()),
+            )
+        }),
+        ("decode_revert" | "decode_error", [data]) => revert_data(data)
+            .get_bytes()
+            .and_then(|data| revert::decode_revert(&data, None)),
+        ("decode_revert" | "decode_error", [data, abi]) => parse_json_abi(abi)
+            .and_then(|abi| revert::decode_revert(&revert_data(data).get_bytes()?, Some(&abi))),
+        ("interface_id" | "erc165_id", sigs) => {
+            selectors::interface_id(sigs).map(serde_json::Value::String)
+        }
+        ("selector_collisions", [a, b]) => selectors::selector_collisions(a, b),
+        ("metadata" | "decode_metadata", [code]) => code
+            .get_bytes()
+            .and_then(|code| metadata::decode_metadata(&code)),
+        ("constructor_args", [input, creation, abi]) => parse_json_abi(abi).and_then(|abi| {
+            metadata::constructor_args(&input.get_bytes()?, &creation.get_bytes()?, &abi)
+        }),
+        // an imported artifact, with both the creation code and the ABI
+        ("constructor_args", [input, artifact @ ParseResult::Json(json)]) => {
+            let creation = json["bytecode"].as_str().unwrap_or_default().to_string();
+            parse_json_abi(artifact).and_then(|abi| {
+                metadata::constructor_args(
+                    &input.get_bytes()?,
+                    &ParseResult::from(creation).get_bytes()?,
+                    &abi,
+                )
+            })
+        }
+        ("multicall_decode", [returndata]) => rpc_result(returndata)
+            .get_bytes()
+            .and_then(|data| multicall::decode_results(&data, &[])),
+        ("multicall_decode", [returndata, sigs]) => {
+            rpc_result(returndata).get_bytes().and_then(|data| {
+                let sigs = match sigs {
+                    ParseResult::Array(sigs) => sigs.iter().map(|sig| sig.to_string()).collect(),
+                    sig => vec![sig.to_string()],
+                };
+                multicall::decode_results(&data, &sigs)
+            })
+        }
+        ("inspect_calldata" | "inspect", [calldata]) => calldata
+            .get_bytes()
+            .and_then(|data| calldata::inspect_calldata(None, &data)),
+        ("inspect_calldata" | "inspect", [ParseResult::String(sig), calldata]) => calldata
+            .get_bytes()
+            .and_then(|data| calldata::inspect_calldata(Some(sig), &data)),
+        _ => return None,
+    };
+    match result {
+        // ie: an interface id, rather than a json string
+        Ok(serde_json::Value::String(result)) => Some(result.into()),
+        Ok(result) => Some(result.into()),
+        Err(e) => {
+            log!(format!("Invalid ABI input: {}", e));
+            Some(ParseResult::NAN)
+        }
+    }
+}
+
+/// Handles the functions reproducing the precompiled contracts, which return their exact
+/// output, ie: `ripemd160` as a 32-byte word.
+fn precompile_fn_args(func: &str, args: &[ParseResult]) -> Option<ParseResult> {
+    let run = |address, input: Result<Vec<u8>, String>| {
+        input.and_then(|input| precompiles::run(address, &input).map(|(output, _)| output))
+    };
+    let result = match (func, args) {
+        ("sha256", [data]) => run(precompiles::SHA256, precompiles::data(data)).map(hex_bytes),
+        ("ripemd160", [data]) => {
+            run(precompiles::RIPEMD160, precompiles::data(data)).map(hex_bytes)
+        }
+        ("identity", [data]) => run(precompiles::IDENTITY, precompiles::data(data)).map(hex_bytes),
+        ("modexp", [input]) => input
+            .get_bytes()
+            .and_then(|input| precompiles::modexp_raw(&input))
+            .map(ParseResult::from),
+        ("modexp", [base, exponent, modulus]) => {
+            precompiles::modexp(base, exponent, modulus).map(ParseResult::from)
+        }
+        ("ec_add" | "ecadd", args) => {
+            run(precompiles::EC_ADD, precompiles::words(args)).map(|p| precompiles::point(&p))
+        }
+        ("ec_mul" | "ecmul", args) => {
+            run(precompiles::EC_MUL, precompiles::words(args)).map(|p| precompiles::point(&p))
+        }
+        ("ec_pairing" | "ecpairing", args) => {
+            run(precompiles::EC_PAIRING, precompiles::words(args))
+                .map(|output| U256::from_be_slice(&output).into())
+        }
+        ("blake2f", [input]) => run(precompiles::BLAKE2F, input.get_bytes()).map(hex_bytes),
+        ("blake2f", [rounds, h, m, t, f]) => run(
+            precompiles::BLAKE2F,
+            precompiles::blake2f_input(rounds, h, m, t, f),
+        )
+        .map(hex_bytes),
+        _ => return None,
+    };
+    match result {
+        Ok(result) => Some(result),
+        Err(e) => {
+            log!(format!("Invalid precompile input: {}", e));
+            Some(ParseResult::NAN)
+        }
+    }
+}
+
+fn hex_bytes(bytes: Vec<u8>) -> ParseResult {
+    format!("0x{}", hex::encode(bytes)).into()
+}
+
+/// Handles the functions that need to know whether an argument is an address
+/// (rather than a plain EVM word) and the EIP-55/EIP-1191 checksum helpers.
+fn address_fn_args(func: &str, args: &[ParseResult]) -> Option<ParseResult> {
+    if !matches!(
+        func,
+        "address" | "addr" | "checksum" | "is_checksummed" | "is_checksum"
+    ) || args.is_empty()
+        || args.len() > 2
+    {
+        return None;
+    }
+    let chain_id = match args.get(1) {
+        Some(ParseResult::Value(id)) => match u64::try_from(*id) {
+            Ok(id) => Some(id),
+            Err(_) => return Some(ParseResult::NAN),
+        },
+        Some(_) => return Some(ParseResult::NAN),
+        None => None,
+    };
+    match func {
+        "is_checksummed" | "is_checksum" => match &args[0] {
+            ParseResult::String(s) => match Address::parse_checksummed(s, chain_id) {
+                Ok(_) => Some("true".into()),
+                Err(_) => Some("false".into()),
+            },
+            // addresses of other blocks, as they are displayed
+            ParseResult::Address(a) => {
+                match Address::parse_checksummed(a.to_checksum(None), chain_id) {
+                    Ok(_) => Some("true".into()),
+                    Err(_) => Some("false".into()),
+                }
+            }
+            _ => Some(ParseResult::NAN),
+        },
+        _ => match &args[0] {
+            ParseResult::Address(a) => Some(a.to_checksum(chain_id).into()),
+            ParseResult::String(s) => match s.parse::<Address>() {
+                Ok(a) => Some(a.to_checksum(chain_id).into()),
+                Err(_) => match s.parse::<U256>() {
+                    Ok(u) => Some(u256_to_address(u).to_checksum(chain_id).into()),
+                    Err(_) => Some(ParseResult::NAN),
+                },
+            },
+            ParseResult::Value(u) => Some(u256_to_address(*u).to_checksum(chain_id).into()),
+            _ => Some(ParseResult::NAN),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval_str(input: &str) -> String {
+        parse(input, &vec![]).to_string()
+    }
+
+    #[test]
+    fn eip191_hash_of_text() {
+        assert_eq!(
+            eval_str("eip191_hash(\"hello\")"),
+            "0x50b2c43fd39106bafbba0da34fc430e1f91e3c96ea2acee2bc34119f92b37750"
+        );
+        assert_eq!(
+            eval_str("hash_message(\"Hello World\")"),
+            "0xa1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+        );
+    }
+
+    #[test]
+    fn eip191_hash_of_hex_literals() {
+        let expected = eip191_hash_message([0x12, 0x34]).to_string();
+        assert_eq!(eval_str("eip191_hash(0x1234)"), expected);
+        assert_eq!(eval_str("eip191_hash(\"0x1234\")"), expected);
+        assert_eq!(
+            eval_str("eip191_hash(0x0000)"),
+            eip191_hash_message([0, 0]).to_string()
+        );
+    }
+
+    #[test]
+    fn precompiles_of_hex_literals() {
+        let expected = "0x3a103a4e5729ad68c02a678ae39accfbc0ae208096437401b7ceab63cca0622f";
+        assert_eq!(eval_str("sha256(0x1234)"), expected);
+        assert_eq!(eval_str("sha256(\"0x1234\")"), expected);
+        // computed numbers are still hashed as 32-byte words
+        assert_eq!(
+            eval_str("sha256(0x1234 + 0)"),
+            "0x730e03974dc00b82b4d4c914b1c59f088d0a6472e8ed592970d384b8e80eea32"
+        );
+        assert_eq!(
+            eval_str("ripemd160(0x1234)"),
+            eval_str("ripemd160(\"0x1234\")")
+        );
+        assert_eq!(eval_str("identity(0x00ff)"), "0x00ff");
+    }
+}
@@ -1,12 +1,16 @@
-use alloy_core::primitives::hex;
+use alloy_core::primitives::{hex, U256};
 use alloy_dyn_abi::FunctionExt;
 use alloy_dyn_abi::JsonAbiExt;
 use alloy_dyn_abi::{DynSolType, DynSolValue};
-use alloy_json_abi::Function;
+use alloy_json_abi::{Function, JsonAbi, Param};
 use serde::{
     ser::{SerializeSeq, SerializeStruct},
     Serialize, Serializer,
 };
+use serde_json::{json, Map, Value};
+
+use super::result::ParseResult;
+use crate::parser::utils::{split_top_level, trim_parentheses, u256_to_address};
 
 /// Decodes the calldata of the function
 pub fn abi_decode_calldata(
@@ -65,16 +69,20 @@ pub fn abi_process_and_decode_calldata(
         format!("0x{}", calldata)
     };
 
-    let selector = if calldata.len() % 64 == 10 {
-        Some(FunctionSelector {
-            fn_selector: calldata[..10].to_string(),
-        })
-    } else {
-        if calldata.len() % 64 != 2 {
-            return (None, Err("invalid calldata length".to_string()));
-        }
-        None
+    // a named signature tells us whether the calldata starts with its selector, otherwise
+    // fall back to the length, as the arguments are a whole number of words
+    let has_selector = match Function::parse(&sig) {
+        Ok(func) if !abi.starts_with("(") => calldata
+            .get(2..10)
+            .is_some_and(|s| s.eq_ignore_ascii_case(&hex::encode(func.selector()))),
+        _ => calldata.len() % 64 == 10,
     };
+    if !has_selector && calldata.len() % 64 != 2 {
+        return (None, Err("invalid calldata length".to_string()));
+    }
+    let selector = has_selector.then(|| FunctionSelector {
+        fn_selector: calldata[..10].to_string(),
+    });
 
     let decoded = abi_decode_calldata(&sig, &calldata, true, selector.is_some());
     (
@@ -83,6 +91,23 @@ pub fn abi_process_and_decode_calldata(
     )
 }
 
+/// Decodes the calldata and returns it as json, with the function selector (if any) prepended.
+pub fn abi_decode_to_json(abi: &str, calldata: &str) -> Result<serde_json::Value, String> {
+    match abi_process_and_decode_calldata(abi, calldata) {
+        (selector, Ok(decoded)) => {
+            let mut json = serde_json::to_value(&decoded).map_err(|e| e.to_string())?;
+            if let (Some(selector), Some(array)) = (selector, json.as_array_mut()) {
+                array.insert(
+                    0,
+                    serde_json::to_value(&selector).map_err(|e| e.to_string())?,
+                );
+            }
+            Ok(json)
+        }
+        (_, Err(e)) => Err(e),
+    }
+}
+
 /// Given a function and a vector of string arguments, it proceeds to convert the args to alloy
 /// [DynSolValue]s and then ABI encode them.
 pub fn encode_function_args(func: &Function, args: Vec<String>) -> Result<Vec<u8>, String> {
@@ -105,17 +130,18 @@ pub fn encode_function_args(func: &Function, args: Vec<String>) -> Result<Vec<u8
     }
 }
 
-pub fn abi_encode(abi: &str, args: Vec<String>, with_selector: bool) -> Result<String, String> {
-    // process abi to get the function signature
+/// Parses a function signature, or a list of types (`(address,uint256)`) as a dummy function.
+pub fn parse_function(abi: &str) -> Result<Function, String> {
     let sig = if abi.starts_with("(") && abi.ends_with(")") {
         format!("dummy_fn{}", abi)
     } else {
         abi.to_string()
     };
-    let func = match Function::parse(&sig) {
-        Ok(func) => func,
-        Err(e) => return Err(format!("could not parse function signature: {}", e)),
-    };
+    Function::parse(&sig).map_err(|e| format!("could not parse function signature: {}", e))
+}
+
+pub fn abi_encode(abi: &str, args: Vec<String>, with_selector: bool) -> Result<String, String> {
+    let func = parse_function(abi)?;
     let calldata = match encode_function_args(&func, args) {
         Ok(res) => hex::encode(res),
         Err(e) => return Err(format!("Could not ABI encode the function and arguments. Did you pass in the right types?\nError\n{}", e)),
@@ -127,6 +153,183 @@ pub fn abi_encode(abi: &str, args: Vec<String>, with_selector: bool) -> Result<S
     }
 }
 
+/// Converts the arguments of the encoding functions to the given types. The values can be
+/// passed as they are (`0xd8da..6045, [1, 2], (0x1234, "a, b")`, tuples being written like
+/// arrays), or listed in a single string (`"0xd8da..6045, [1, 2], (0x1234, 'a, b')"`).
+fn encode_args(types: &[DynSolType], args: &[ParseResult]) -> Result<Vec<DynSolValue>, String> {
+    let values = match args {
+        [ParseResult::String(list)] if types != [DynSolType::String] => {
+            let mut items = split_top_level(list.trim());
+            if items.len() == 1 && types.len() > 1 {
+                items = split_top_level(trim_parentheses(list.trim()));
+            }
+            if items.len() != types.len() {
+                return Err(format!(
+                    "expected {} values, found {}",
+                    types.len(),
+                    items.len()
+                ));
+            }
+            std::iter::zip(types, items)
+                .map(|(ty, item)| {
+                    ty.coerce_str(&item)
+                        .map_err(|e| format!("failed to coerce value: {}", e))
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        _ => {
+            if args.len() != types.len() {
+                return Err(format!(
+                    "expected {} values, found {}",
+                    types.len(),
+                    args.len()
+                ));
+            }
+            std::iter::zip(types, args)
+                .map(|(ty, arg)| to_dyn_value(ty, arg))
+                .collect::<Result<Vec<_>, _>>()?
+        }
+    };
+    Ok(values)
+}
+
+/// Converts a value of the language to an ABI type. Hex literals are numbers, so they are
+/// right-aligned in fixed-size bytes (ie: `bytes4` from `0xa9059cbb`), and dynamic bytes are
+/// only read from hex strings, as numbers don't keep their leading zero bytes.
+fn to_dyn_value(ty: &DynSolType, arg: &ParseResult) -> Result<DynSolValue, String> {
+    let list = |types: &mut dyn Iterator<Item = &DynSolType>, items: &[ParseResult]| {
+        types
+            .zip(items)
+            .map(|(ty, item)| to_dyn_value(ty, item))
+            .collect::<Result<Vec<_>, String>>()
+    };
+    match (ty, arg) {
+        (DynSolType::Array(inner), ParseResult::Array(items)) => Ok(DynSolValue::Array(list(
+            &mut std::iter::repeat(inner.as_ref()),
+            items,
+        )?)),
+        (DynSolType::FixedArray(inner, len), ParseResult::Array(items)) if items.len() == *len => {
+            Ok(DynSolValue::FixedArray(list(
+                &mut std::iter::repeat(inner.as_ref()),
+                items,
+            )?))
+        }
+        (DynSolType::Tuple(types), ParseResult::Array(items)) if items.len() == types.len() => {
+            Ok(DynSolValue::Tuple(list(&mut types.iter(), items)?))
+        }
+        (_, ParseResult::Array(_)) => Err(format!("unexpected list for {}", ty)),
+        (DynSolType::String, ParseResult::String(s)) => Ok(DynSolValue::String(s.to_string())),
+        (DynSolType::Address, ParseResult::Value(u)) => {
+            coerce(ty, &u256_to_address(*u).to_string())
+        }
+        (DynSolType::Bool, ParseResult::Value(u)) if *u <= U256::from(1) => {
+            Ok(DynSolValue::Bool(!u.is_zero()))
+        }
+        (DynSolType::FixedBytes(size), ParseResult::Value(u)) => {
+            if u.byte_len() > *size {
+                return Err(format!("{} doesn't fit in bytes{}", u, size));
+            }
+            let bytes = u.to_be_bytes::<32>();
+            coerce(ty, &format!("0x{}", hex::encode(&bytes[32 - size..])))
+        }
+        (DynSolType::Bytes, ParseResult::String(_) | ParseResult::Value(_)) => {
+            arg.get_bytes().map(DynSolValue::Bytes)
+        }
+        (_, ParseResult::NAN) => Err(format!("invalid value for {}", ty)),
+        (_, arg) => coerce(ty, &arg.to_string()),
+    }
+}
+
+fn coerce(ty: &DynSolType, value: &str) -> Result<DynSolValue, String> {
+    ty.coerce_str(value)
+        .map_err(|e| format!("failed to coerce value: {}", e))
+}
+
+/// Parses a list of types, ie: `(address,uint256)` or `address,uint256`.
+fn parse_types(types: &str) -> Result<Vec<DynSolType>, String> {
+    split_top_level(trim_parentheses(types.trim()))
+        .iter()
+        .filter(|ty| !ty.is_empty())
+        .map(|ty| DynSolType::parse(ty).map_err(|e| format!("failed to parse type: {}", e)))
+        .collect()
+}
+
+/// ABI encodes the values for a function signature (or a list of types), with or without the
+/// selector.
+pub fn abi_encode_values(
+    abi: &str,
+    args: &[ParseResult],
+    with_selector: bool,
+) -> Result<String, String> {
+    let func = parse_function(abi)?;
+    let types = func
+        .inputs
+        .iter()
+        .map(|input| {
+            DynSolType::parse(&input.selector_type())
+                .map_err(|e| format!("failed to parse type: {}", e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let values = encode_args(&types, args)?;
+    let calldata = func
+        .abi_encode_input(&values)
+        .map_err(|e| format!("failed to encode function arguments: {}", e))?;
+    if with_selector {
+        Ok(format!("0x{}", hex::encode(calldata)))
+    } else {
+        Ok(format!("0x{}", hex::encode(&calldata[4..])))
+    }
+}
+
+/// Solidity's `abi.encodePacked`: values take their minimal size and dynamic types are encoded
+/// in place without their length, while array elements are padded to 32 bytes. Like solc,
+/// structs, nested arrays and arrays of dynamic types are rejected.
+pub fn abi_encode_packed(types: &str, args: &[ParseResult]) -> Result<String, String> {
+    let values = encode_args(&parse_types(types)?, args)?;
+    for value in values.iter() {
+        match value.as_type() {
+            Some(DynSolType::Tuple(_)) => {
+                return Err("structs can't be packed".to_string());
+            }
+            Some(DynSolType::Array(inner) | DynSolType::FixedArray(inner, _))
+                if !matches!(
+                    *inner,
+                    DynSolType::Address
+                        | DynSolType::Bool
+                        | DynSolType::Int(_)
+                        | DynSolType::Uint(_)
+                        | DynSolType::FixedBytes(_)
+                ) =>
+            {
+                return Err("only arrays of static, non-array types can be packed".to_string());
+            }
+            _ => {}
+        }
+    }
+    let mut packed = Vec::new();
+    for value in values.iter() {
+        match value {
+            // array elements take a word each, padded like `abi.encode` does (ie: bytesN on
+            // the right), where alloy left-pads them all
+            DynSolValue::Array(items) | DynSolValue::FixedArray(items) => {
+                packed.extend(items.iter().flat_map(DynSolValue::abi_encode))
+            }
+            value => packed.extend(value.abi_encode_packed()),
+        }
+    }
+    Ok(format!("0x{}", hex::encode(packed)))
+}
+
+/// `abi.encode(a, b, ..)`: the head/tail encoding of the values, without a selector and without
+/// the offset that wraps a single dynamic tuple.
+pub fn abi_encode_params(types: &str, args: &[ParseResult]) -> Result<String, String> {
+    let values = encode_args(&parse_types(types)?, args)?;
+    Ok(format!(
+        "0x{}",
+        hex::encode(DynSolValue::Tuple(values).abi_encode_params())
+    ))
+}
+
 #[derive(Debug)]
 pub struct Encodable(DynSolValue);
 
@@ -181,7 +384,10 @@ impl Serialize for Encodable {
                 state.serialize_field("string", v)?;
                 state.end()
             }
-            DynSolValue::Array(v) | DynSolValue::FixedArray(v) | DynSolValue::Tuple(v) => {
+            DynSolValue::Array(v)
+            | DynSolValue::FixedArray(v)
+            | DynSolValue::Tuple(v)
+            | DynSolValue::CustomStruct { tuple: v, .. } => {
                 let mut seq = serializer.serialize_seq(Some(v.len()))?;
                 for elem in v.iter() {
                     seq.serialize_element(&Encodable(elem.clone()))?;
@@ -197,3 +403,201 @@ impl Serialize for Encodable {
 pub struct FunctionSelector {
     fn_selector: String,
 }
+
+/// Builds a [JsonAbi] from a block: a JSON ABI array, a compiler artifact (`{"abi": [..]}`)
+/// or a list of human-readable signatures (`["function transfer(address to, uint256 amount)"]`).
+pub fn parse_json_abi(abi: &ParseResult) -> Result<JsonAbi, String> {
+    match abi {
+        ParseResult::Json(Value::Object(obj)) => match obj.get("abi") {
+            Some(abi) => parse_json_abi(&ParseResult::Json(abi.clone())),
+            None => Err("expected a JSON ABI".to_string()),
+        },
+        ParseResult::Json(Value::Array(items)) if items.iter().all(Value::is_string) => {
+            parse_human_readable_abi(items.iter().filter_map(Value::as_str))
+        }
+        ParseResult::Json(json) => {
+            serde_json::from_value(json.clone()).map_err(|e| format!("invalid JSON ABI: {}", e))
+        }
+        ParseResult::Array(items) => {
+            let items = items
+                .iter()
+                .map(|item| item.get_string().ok_or("expected a list of signatures"))
+                .collect::<Result<Vec<String>, _>>()?;
+            parse_human_readable_abi(items.iter().map(String::as_str))
+        }
+        ParseResult::String(s) => parse_human_readable_abi(std::iter::once(s.as_str())),
+        _ => Err("expected a JSON ABI".to_string()),
+    }
+}
+
+/// Parses human-readable signatures, assuming `function` when the keyword is omitted.
+fn parse_human_readable_abi<'a>(items: impl Iterator<Item = &'a str>) -> Result<JsonAbi, String> {
+    let items: Vec<String> = items
+        .map(|item| {
+            let item = item.trim();
+            match item.split(|c: char| c.is_whitespace() || c == '(').next() {
+                Some("function" | "event" | "error" | "constructor" | "fallback" | "receive") => {
+                    item.to_string()
+                }
+                _ => format!("function {}", item),
+            }
+        })
+        .collect();
+    JsonAbi::parse(items.iter().map(String::as_str))
+        .map_err(|e| format!("invalid human-readable ABI: {}", e))
+}
+
+/// Decodes calldata against an ABI, picking the function from the selector, and returns the
+/// arguments keyed by their type and name.
+pub fn decode_calldata(abi: &JsonAbi, calldata: &[u8]) -> Result<Value, String> {
+    if calldata.len() < 4 {
+        return Err("calldata is shorter than a selector".to_string());
+    }
+    let selector = &calldata[..4];
+    let func = abi
+        .functions()
+        .find(|f| f.selector().as_slice() == selector)
+        .ok_or_else(|| format!("no function with selector 0x{}", hex::encode(selector)))?;
+    let values = func
+        .abi_decode_input(&calldata[4..], false)
+        .map_err(|e| format!("failed to decode calldata: {}", e))?;
+    Ok(json!({
+        "function": func.signature(),
+        "selector": format!("0x{}", hex::encode(selector)),
+        "args": named_values_to_json(&values, &func.inputs),
+    }))
+}
+
+/// Decodes calldata against every candidate function for its selector (ie: from the signature
+/// database). The candidates that decode cleanly are ranked first when re-encoding the values
+/// gives back the exact calldata, as selector collisions rarely do.
+pub fn decode_calldata_candidates(
+    functions: &[Function],
+    calldata: &[u8],
+) -> Result<Value, String> {
+    if calldata.len() < 4 {
+        return Err("calldata is shorter than a selector".to_string());
+    }
+    let mut decoded = functions
+        .iter()
+        .filter_map(|func| {
+            let values = func.abi_decode_input(&calldata[4..], true).ok()?;
+            let exact = func
+                .abi_encode_input(&values)
+                .is_ok_and(|encoded| encoded == calldata);
+            Some((
+                exact,
+                json!({
+                    "function": func.signature(),
+                    "exact": exact,
+                    "args": named_values_to_json(&values, &func.inputs),
+                }),
+            ))
+        })
+        .collect::<Vec<_>>();
+    if decoded.is_empty() {
+        return Err(format!(
+            "no known function decodes calldata with selector 0x{}",
+            hex::encode(&calldata[..4])
+        ));
+    }
+    decoded.sort_by_key(|(exact, _)| !exact);
+    Ok(Value::Array(
+        decoded.into_iter().map(|(_, json)| json).collect(),
+    ))
+}
+
+/// Converts decoded values to a json object keyed by `"<type> <name>"`, falling back to the
+/// position for unnamed params.
+pub fn named_values_to_json(values: &[DynSolValue], params: &[Param]) -> Value {
+    let mut map = Map::new();
+    for (i, value) in values.iter().enumerate() {
+        let (key, components) = match params.get(i) {
+            Some(param) if param.name.is_empty() => (
+                format!("{} [{}]", param.selector_type(), i),
+                &param.components[..],
+            ),
+            Some(param) => (
+                format!("{} {}", param.selector_type(), param.name),
+                &param.components[..],
+            ),
+            None => (format!("[{}]", i), &[][..]),
+        };
+        map.insert(key, dyn_value_to_json(value, components));
+    }
+    Value::Object(map)
+}
+
+/// Converts a decoded value to json, naming the tuple fields after the ABI components.
+pub fn dyn_value_to_json(value: &DynSolValue, components: &[Param]) -> Value {
+    match value {
+        DynSolValue::Bool(b) => json!(b),
+        DynSolValue::Int(i, _) => json!(i.to_string()),
+        DynSolValue::Uint(u, _) => json!(u.to_string()),
+        DynSolValue::FixedBytes(w, size) => json!(format!("0x{}", hex::encode(&w[..*size]))),
+        DynSolValue::Address(a) => json!(a.to_checksum(None)),
+        DynSolValue::Function(f) => json!(f.to_string()),
+        DynSolValue::Bytes(b) => json!(format!("0x{}", hex::encode(b))),
+        DynSolValue::String(s) => json!(s),
+        DynSolValue::Array(v) | DynSolValue::FixedArray(v) => Value::Array(
+            v.iter()
+                .map(|elem| dyn_value_to_json(elem, components))
+                .collect(),
+        ),
+        DynSolValue::Tuple(v) | DynSolValue::CustomStruct { tuple: v, .. } => {
+            named_values_to_json(v, components)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packed(types: &str, args: &[ParseResult]) -> String {
+        abi_encode_packed(types, args).unwrap()
+    }
+
+    #[test]
+    fn encode_packed_solidity_docs_example() {
+        // abi.encodePacked(int16(-1), bytes1(0x42), uint16(0x03), string("Hello, world!"))
+        assert_eq!(
+            packed(
+                "int16,bytes1,uint16,string",
+                &[
+                    "-1".into(),
+                    "0x42".into(),
+                    U256::from(3).into(),
+                    "Hello, world!".into()
+                ]
+            ),
+            "0xffff42000348656c6c6f2c20776f726c6421"
+        );
+    }
+
+    #[test]
+    fn encode_packed_pads_array_elements_like_solc() {
+        // abi.encodePacked([bytes4(0x12345678)])
+        assert_eq!(
+            packed("bytes4[]", &[vec![ParseResult::from("0x12345678")].into()]),
+            format!("0x12345678{}", "00".repeat(28))
+        );
+        // abi.encodePacked([int8(-1)], [uint8(1)], address(1))
+        assert_eq!(
+            packed(
+                "int8[1],uint8[],address",
+                &[
+                    vec![ParseResult::from("-1")].into(),
+                    vec![ParseResult::from(U256::from(1))].into(),
+                    U256::from(1).into(),
+                ]
+            ),
+            format!(
+                "0x{}{}01{}01",
+                "ff".repeat(32),
+                "00".repeat(31),
+                "00".repeat(19)
+            )
+        );
+    }
+}
@@ -1,11 +1,13 @@
 #![allow(dead_code)]
-use alloy_core::primitives::{B256, U256};
+use alloy_core::primitives::{hex, Address, B256, U256};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ParseResult {
     Value(U256),
     String(String),
+    Address(Address),
+    Array(Vec<ParseResult>),
     Json(serde_json::Value),
     NAN,
 }
@@ -17,6 +19,7 @@ impl ParseResult {
 
     pub fn is_address(&self) -> bool {
         match self {
+            Self::Address(_) => true,
             Self::String(s) => s.starts_with("0x") && s.len() == 42,
             _ => false,
         }
@@ -26,6 +29,10 @@ impl ParseResult {
         matches!(self, Self::Value(_))
     }
 
+    pub fn is_array(&self) -> bool {
+        matches!(self, Self::Array(_))
+    }
+
     pub fn is_nan(&self) -> bool {
         matches!(self, Self::NAN)
     }
@@ -47,6 +54,26 @@ impl ParseResult {
         }
     }
 
+    /// Raw bytes of a hex string. Numbers are rejected, as they don't keep their leading zero
+    /// bytes: hex literals passed where bytes are expected are kept as strings instead.
+    pub fn get_bytes(&self) -> Result<Vec<u8>, String> {
+        match self {
+            Self::String(s) => hex::decode(s).map_err(|e| format!("invalid hex bytes: {}", e)),
+            Self::Value(_) => {
+                Err("expected hex bytes rather than a number, ie: \"0x00ff\"".to_string())
+            }
+            _ => Err("expected hex bytes".to_string()),
+        }
+    }
+
+    /// Addresses behave like EVM words in arithmetic and in functions that expect numbers.
+    pub fn address_to_value(self) -> Self {
+        match self {
+            Self::Address(a) => Self::Value(U256::from_be_slice(a.as_slice())),
+            other => other,
+        }
+    }
+
     pub fn to_hex_string(&self, full_evm_word: bool) -> String {
         match self {
             ParseResult::Value(u) => {
@@ -65,6 +92,14 @@ impl ParseResult {
                 }
             }
             ParseResult::String(s) => s.to_string(),
+            ParseResult::Address(a) => a.to_checksum(None),
+            ParseResult::Array(v) => format!(
+                "[{}]",
+                v.iter()
+                    .map(|i| i.to_hex_string(full_evm_word))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
             ParseResult::Json(j) => j.to_string(),
             ParseResult::NAN => "-".to_string(),
         }
@@ -74,6 +109,14 @@ impl ParseResult {
         match self {
             ParseResult::Value(u) => u.to_string(),
             ParseResult::String(s) => s.to_string(),
+            ParseResult::Address(a) => a.to_checksum(None),
+            ParseResult::Array(v) => format!(
+                "[{}]",
+                v.iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
             ParseResult::Json(j) => j.to_string(),
             ParseResult::NAN => "-".to_string(),
         }
@@ -129,6 +172,18 @@ impl From<Option<String>> for ParseResult {
     }
 }
 
+impl From<Address> for ParseResult {
+    fn from(a: Address) -> Self {
+        ParseResult::Address(a)
+    }
+}
+
+impl From<Vec<ParseResult>> for ParseResult {
+    fn from(v: Vec<ParseResult>) -> Self {
+        ParseResult::Array(v)
+    }
+}
+
 impl From<serde_json::Value> for ParseResult {
     fn from(v: serde_json::Value) -> Self {
         ParseResult::Json(v)
@@ -140,6 +195,14 @@ impl ToString for ParseResult {
         match self {
             ParseResult::Value(u) => u.to_string(),
             ParseResult::String(s) => s.to_string(),
+            ParseResult::Address(a) => a.to_checksum(None),
+            ParseResult::Array(v) => format!(
+                "[{}]",
+                v.iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
             ParseResult::Json(j) => j.to_string(),
             ParseResult::NAN => "-".to_string(),
         }
@@ -0,0 +1,148 @@
+use alloy_core::primitives::hex;
+use alloy_json_abi::JsonAbi;
+use serde_json::{json, Map, Value};
+
+/// Parses a Foundry (`out/Counter.sol/Counter.json`) or Hardhat
+/// (`artifacts/contracts/Counter.sol/Counter.json`) artifact into
+/// `{"contract": name, "abi": [..], "bytecode": "0x..", "deployedBytecode": "0x..",
+/// "methodIdentifiers": {..}, "storageLayout": {..}}`.
+///
+/// Foundry nests the bytecodes in `{"object": "0x.."}` and names the contract in its metadata,
+/// while Hardhat names it `contractName`; both fall back to the file name. Hardhat doesn't
+/// include the method identifiers, so they're computed from the ABI, and neither includes the
+/// storage layout unless the compiler was asked for it.
+pub fn parse_artifact(artifact: &Value, file_name: &str) -> Result<Value, String> {
+    let abi = artifact
+        .get("abi")
+        .filter(|abi| abi.is_array())
+        .ok_or("expected an artifact with an ABI")?;
+    let parsed: JsonAbi =
+        serde_json::from_value(abi.clone()).map_err(|e| format!("invalid ABI: {}", e))?;
+
+    let mut contract = Map::new();
+    contract.insert(
+        "contract".to_string(),
+        json!(contract_name(artifact, file_name)),
+    );
+    contract.insert("abi".to_string(), abi.clone());
+    for key in ["bytecode", "deployedBytecode"] {
+        let bytecode = match &artifact[key] {
+            Value::String(bytecode) => bytecode.as_str(),
+            value => value["object"].as_str().unwrap_or_default(),
+        };
+        let bytecode = bytecode.strip_prefix("0x").unwrap_or(bytecode);
+        contract.insert(key.to_string(), json!(format!("0x{}", bytecode)));
+    }
+    let method_identifiers = match artifact.get("methodIdentifiers") {
+        Some(Value::Object(ids)) => Value::Object(ids.clone()),
+        _ => parsed
+            .functions()
+            .map(|f| (f.signature(), json!(hex::encode(f.selector()))))
+            .collect::<Map<String, Value>>()
+            .into(),
+    };
+    contract.insert("methodIdentifiers".to_string(), method_identifiers);
+    if let Some(layout) = artifact.get("storageLayout").filter(|l| l.is_object()) {
+        contract.insert("storageLayout".to_string(), layout.clone());
+    }
+    Ok(Value::Object(contract))
+}
+
+fn contract_name(artifact: &Value, file_name: &str) -> String {
+    if let Some(name) = artifact["contractName"].as_str() {
+        return name.to_string();
+    }
+    // older Foundry versions keep the metadata as a string
+    let metadata = match &artifact["metadata"] {
+        Value::String(metadata) => serde_json::from_str(metadata).unwrap_or_default(),
+        metadata => metadata.clone(),
+    };
+    let target = metadata["settings"]["compilationTarget"].as_object();
+    match target.and_then(|target| target.values().next()?.as_str()) {
+        Some(name) => name.to_string(),
+        None => file_name
+            .rsplit('/')
+            .next()
+            .unwrap_or(file_name)
+            .trim_end_matches(".json")
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi() -> Value {
+        json!([{
+            "type": "function",
+            "name": "increment",
+            "inputs": [],
+            "outputs": [],
+            "stateMutability": "nonpayable",
+        }])
+    }
+
+    #[test]
+    fn foundry_artifact() {
+        let artifact = json!({
+            "abi": abi(),
+            "bytecode": { "object": "0x6080", "linkReferences": {} },
+            "deployedBytecode": { "object": "0x6081" },
+            "methodIdentifiers": { "increment()": "d09de08a" },
+            "metadata": {
+                "settings": { "compilationTarget": { "src/Counter.sol": "Counter" } },
+            },
+        });
+        let contract = parse_artifact(&artifact, "Counter.json").unwrap();
+        assert_eq!(contract["contract"], "Counter");
+        assert_eq!(contract["bytecode"], "0x6080");
+        assert_eq!(contract["deployedBytecode"], "0x6081");
+        assert_eq!(contract["methodIdentifiers"]["increment()"], "d09de08a");
+        assert!(contract.get("storageLayout").is_none());
+
+        // older versions keep the metadata as a string
+        let metadata = json!({
+            "settings": { "compilationTarget": { "src/Counter.sol": "Counter" } },
+        });
+        let artifact = json!({ "abi": abi(), "metadata": metadata.to_string() });
+        assert_eq!(contract_name(&artifact, "out/Other.json"), "Counter");
+    }
+
+    #[test]
+    fn hardhat_artifact() {
+        let artifact = json!({
+            "_format": "hh-sol-artifact-1",
+            "contractName": "Token",
+            "sourceName": "contracts/Token.sol",
+            "abi": abi(),
+            "bytecode": "0x6080",
+            "deployedBytecode": "6081",
+            "linkReferences": {},
+            "deployedLinkReferences": {},
+        });
+        let contract = parse_artifact(&artifact, "Counter.json").unwrap();
+        assert_eq!(contract["contract"], "Token");
+        assert_eq!(contract["bytecode"], "0x6080");
+        assert_eq!(contract["deployedBytecode"], "0x6081");
+        // computed from the ABI
+        assert_eq!(contract["methodIdentifiers"]["increment()"], "d09de08a");
+    }
+
+    #[test]
+    fn contract_name_falls_back_to_the_file_name() {
+        assert_eq!(
+            contract_name(&json!({ "abi": [] }), "out/Counter.sol/Counter.json"),
+            "Counter"
+        );
+        assert_eq!(contract_name(&json!({}), "Counter.json"), "Counter");
+    }
+
+    #[test]
+    fn not_an_artifact() {
+        assert!(parse_artifact(&json!({ "blocks": [] }), "notebook.json").is_err());
+        assert!(parse_artifact(&json!({ "abi": "[]" }), "Counter.json").is_err());
+        assert!(parse_artifact(&json!([{ "label": "block_0" }]), "notebook.json").is_err());
+        assert!(parse_artifact(&json!({ "abi": [{ "type": "event" }] }), "x.json").is_err());
+    }
+}
@@ -0,0 +1,456 @@
+use super::signatures;
+use crate::parser::types::abi::parse_function;
+
+use alloy_core::primitives::{hex, U256};
+use alloy_dyn_abi::{DynSolType, JsonAbiExt};
+use alloy_json_abi::Param;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+
+/// Annotates every 32-byte word of ABI encoded calldata with the part of the encoding it
+/// belongs to: head slots of the parameters, offset pointers, length words, tail elements and
+/// padding. Without a signature, the function is looked up in the signature database.
+///
+/// The layout is followed as encoded (offsets are read from the calldata, not recomputed), so
+/// hand-crafted encodings with overlapping or out of bounds offsets are shown as they are.
+pub fn inspect_calldata(sig: Option<&str>, calldata: &[u8]) -> Result<Value, String> {
+    let (func, has_selector) = match sig {
+        Some(sig) => {
+            let func = parse_function(sig)?;
+            let has_selector = if sig.starts_with('(') {
+                calldata.len() % 32 == 4
+            } else if calldata.starts_with(func.selector().as_slice()) {
+                true
+            } else if calldata.len().is_multiple_of(32) {
+                false
+            } else {
+                return Err(format!(
+                    "calldata doesn't start with the selector of {}",
+                    func.signature()
+                ));
+            };
+            (func, has_selector)
+        }
+        None => {
+            if calldata.len() < 4 {
+                return Err("calldata is shorter than a selector".to_string());
+            }
+            let func = signatures::functions(&calldata[..4])
+                .iter()
+                .find(|func| func.abi_decode_input(&calldata[4..], false).is_ok())
+                .ok_or("unknown selector, pass the function signature")?;
+            (func.clone(), true)
+        }
+    };
+    let (selector, params) = calldata.split_at(if has_selector { 4 } else { 0 });
+    let fields = func
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(i, param)| Field::new(param, param_name(param, i)))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut layout = Layout {
+        data: params,
+        labels: vec![Vec::new(); params.len().div_ceil(32)],
+        visited: HashSet::new(),
+        budget: 4 * params.len().div_ceil(32) + 64,
+    };
+    let error = layout.tuple(&fields, 0, None).err();
+    let head_words = fields.iter().map(|f| f.head_words()).sum::<usize>();
+
+    let mut result = Map::new();
+    if !sig.is_some_and(|sig| sig.starts_with('(')) {
+        result.insert("function".to_string(), json!(func.signature()));
+    }
+    if has_selector {
+        result.insert(
+            "selector".to_string(),
+            json!(format!("0x{}", hex::encode(selector))),
+        );
+    }
+    let rows = params
+        .chunks(32)
+        .zip(layout.labels)
+        .enumerate()
+        .map(|(i, (word, labels))| {
+            let param = labels.first().map(|(param, _)| *param);
+            let label = match labels.is_empty() {
+                true => "unused".to_string(),
+                false => labels
+                    .into_iter()
+                    .map(|(_, label)| label)
+                    .collect::<Vec<_>>()
+                    .join(" / "),
+            };
+            json!({
+                "offset": format!("0x{:04x}", i * 32),
+                "word": hex::encode(word),
+                "section": if i < head_words { "head" } else { "tail" },
+                "label": label,
+                "param": param,
+            })
+        })
+        .collect::<Vec<_>>();
+    result.insert("layout".to_string(), Value::Array(rows));
+    if let Some(error) = error {
+        result.insert("error".to_string(), json!(error));
+    }
+    Ok(Value::Object(result))
+}
+
+/// A parameter, or a member of a tuple or an array, with the path to it.
+struct Field {
+    ty: DynSolType,
+    name: String,
+    components: Vec<Param>,
+}
+
+impl Field {
+    fn new(param: &Param, name: String) -> Result<Self, String> {
+        let ty = DynSolType::parse(&param.selector_type())
+            .map_err(|e| format!("failed to parse type: {}", e))?;
+        Ok(Self {
+            ty,
+            name,
+            components: param.components.clone(),
+        })
+    }
+
+    /// Members of a tuple (named after the struct fields) or elements of an array.
+    fn children(&self, len: usize) -> Vec<Field> {
+        match &self.ty {
+            DynSolType::Tuple(types) => types
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| {
+                    let component = self.components.get(i);
+                    Field {
+                        ty: ty.clone(),
+                        name: match component.filter(|c| !c.name.is_empty()) {
+                            Some(c) => format!("{}.{}", self.name, c.name),
+                            None => format!("{}.{}", self.name, i),
+                        },
+                        components: component.map_or(Vec::new(), |c| c.components.clone()),
+                    }
+                })
+                .collect(),
+            DynSolType::Array(inner) | DynSolType::FixedArray(inner, _) => (0..len)
+                .map(|i| Field {
+                    ty: inner.as_ref().clone(),
+                    name: format!("{}[{}]", self.name, i),
+                    components: self.components.clone(),
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Number of words taken in the head of the enclosing tuple.
+    fn head_words(&self) -> usize {
+        if is_dynamic(&self.ty) {
+            1
+        } else {
+            self.ty.minimum_words()
+        }
+    }
+}
+
+struct Layout<'a> {
+    data: &'a [u8],
+    /// Labels of each word, with the index of the parameter they belong to.
+    labels: Vec<Vec<(usize, String)>>,
+    /// Tails already annotated, by position and type. Offsets may point to the same tail, which
+    /// would otherwise be walked again for every pointer (exponentially so in nested arrays).
+    visited: HashSet<(usize, String)>,
+    /// Fields left to annotate. Well-formed calldata takes about one per word, while distinct
+    /// offsets into overlapping arrays could take a quadratic number.
+    budget: usize,
+}
+
+impl Layout<'_> {
+    fn mark(&mut self, position: usize, param: usize, label: String) {
+        if let Some(labels) = self.labels.get_mut(position / 32) {
+            labels.push((param, label));
+        }
+    }
+
+    fn read(&self, position: usize) -> Result<usize, String> {
+        let word = self
+            .data
+            .get(position..position + 32)
+            .ok_or(format!("word at 0x{:04x} is out of bounds", position))?;
+        usize::try_from(U256::from_be_slice(word))
+            .ok()
+            .filter(|value| *value <= self.data.len())
+            .ok_or(format!("value at 0x{:04x} is out of bounds", position))
+    }
+
+    /// Annotates the fields encoded from `base`: static fields in place, dynamic ones through an
+    /// offset relative to `base`.
+    fn tuple(&mut self, fields: &[Field], base: usize, param: Option<usize>) -> Result<(), String> {
+        let mut head = base;
+        for (i, field) in fields.iter().enumerate() {
+            if self.budget == 0 {
+                return Err("too many overlapping offsets to annotate".to_string());
+            }
+            self.budget -= 1;
+            let param = param.unwrap_or(i);
+            if is_dynamic(&field.ty) {
+                let offset = self.read(head)?;
+                self.mark(
+                    head,
+                    param,
+                    format!("offset of {} {} → 0x{:x}", field.ty, field.name, offset),
+                );
+                if !(base + offset).is_multiple_of(32) {
+                    return Err(format!("unaligned offset at 0x{:04x}", head));
+                }
+                self.tail(field, base + offset, param)?;
+            } else {
+                self.value(field, head, param)?;
+            }
+            head += field.head_words() * 32;
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, field: &Field, position: usize, param: usize) -> Result<(), String> {
+        match &field.ty {
+            DynSolType::Tuple(types) => {
+                self.tuple(&field.children(types.len()), position, Some(param))
+            }
+            DynSolType::FixedArray(_, len) => self.fixed_array(field, *len, position, param),
+            ty => {
+                if position + 32 > self.data.len() {
+                    return Err(format!("calldata is truncated at 0x{:04x}", position));
+                }
+                self.mark(position, param, format!("{} {}", ty, field.name));
+                Ok(())
+            }
+        }
+    }
+
+    /// Elements take a word at least, which bounds the length of the arrays worth walking.
+    fn fixed_array(
+        &mut self,
+        field: &Field,
+        len: usize,
+        position: usize,
+        param: usize,
+    ) -> Result<(), String> {
+        if len > self.data.len() / 32 {
+            return Err(format!("{} {} is truncated", field.ty, field.name));
+        }
+        self.tuple(&field.children(len), position, Some(param))
+    }
+
+    fn tail(&mut self, field: &Field, position: usize, param: usize) -> Result<(), String> {
+        if !self.visited.insert((position, field.ty.to_string())) {
+            return Ok(());
+        }
+        match &field.ty {
+            DynSolType::Bytes | DynSolType::String => {
+                let len = self.read(position)?;
+                self.mark(
+                    position,
+                    param,
+                    format!("length of {} {}: {} bytes", field.ty, field.name, len),
+                );
+                if position + 32 + len > self.data.len() {
+                    return Err(format!("{} {} is truncated", field.ty, field.name));
+                }
+                for i in 0..len.div_ceil(32) {
+                    let label = if i == len / 32 {
+                        "data + padding"
+                    } else {
+                        "data"
+                    };
+                    self.mark(
+                        position + 32 * (i + 1),
+                        param,
+                        format!("{} {} {}", field.ty, field.name, label),
+                    );
+                }
+                Ok(())
+            }
+            DynSolType::Array(_) => {
+                let len = self.read(position)?;
+                self.mark(
+                    position,
+                    param,
+                    format!("length of {} {}: {}", field.ty, field.name, len),
+                );
+                // elements take a word at least
+                if len > self.data.len().saturating_sub(position + 32) / 32 {
+                    return Err(format!("{} {} is truncated", field.ty, field.name));
+                }
+                self.tuple(&field.children(len), position + 32, Some(param))
+            }
+            DynSolType::FixedArray(_, len) => self.fixed_array(field, *len, position, param),
+            DynSolType::Tuple(types) => {
+                self.tuple(&field.children(types.len()), position, Some(param))
+            }
+            _ => self.value(field, position, param),
+        }
+    }
+}
+
+fn is_dynamic(ty: &DynSolType) -> bool {
+    match ty {
+        DynSolType::Bytes | DynSolType::String | DynSolType::Array(_) => true,
+        DynSolType::FixedArray(inner, _) => is_dynamic(inner),
+        DynSolType::Tuple(types) => types.iter().any(is_dynamic),
+        _ => false,
+    }
+}
+
+/// Unnamed parameters are referred to by their index, as in the decoded calldata.
+fn param_name(param: &Param, index: usize) -> String {
+    if param.name.is_empty() {
+        format!("[{}]", index)
+    } else {
+        param.name.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(values: &[usize]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|v| U256::from(*v).to_be_bytes::<32>())
+            .collect()
+    }
+
+    fn labels(result: &Value) -> Vec<&str> {
+        result["layout"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["label"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn static_params() {
+        let mut calldata = hex::decode("a9059cbb").unwrap();
+        calldata.extend(words(&[0xaa, 1000, 7]));
+        let result =
+            inspect_calldata(Some("transfer(address to, uint256 amount)"), &calldata).unwrap();
+        assert_eq!(result["function"], "transfer(address,uint256)");
+        assert_eq!(result["selector"], "0xa9059cbb");
+        assert_eq!(labels(&result), ["address to", "uint256 amount", "unused"]);
+        assert_eq!(result["layout"][1]["offset"], "0x0020");
+        assert_eq!(result["layout"][1]["section"], "head");
+        assert_eq!(result["layout"][1]["param"], 1);
+        assert_eq!(result["layout"][2]["section"], "tail");
+        assert_eq!(result["layout"][2]["param"], Value::Null);
+        assert!(result.get("error").is_none());
+
+        // a signature without a name takes calldata without a selector
+        let result = inspect_calldata(Some("(address,uint256)"), &calldata[4..]).unwrap();
+        assert!(result.get("function").is_none());
+        assert!(result.get("selector").is_none());
+        assert_eq!(labels(&result), ["address [0]", "uint256 [1]", "unused"]);
+
+        assert!(inspect_calldata(Some("approve(address,uint256)"), &calldata).is_err());
+        assert!(inspect_calldata(None, &calldata[..3]).is_err());
+    }
+
+    #[test]
+    fn nested_dynamic_params() {
+        let mut calldata = words(&[0x40, 0x140, 2, 0x40, 0xa0, 2, 1, 2, 1, 3, 5]);
+        calldata.extend(b"hello");
+        calldata.extend([0; 27]);
+        let result = inspect_calldata(Some("(uint256[][] a, bytes b)"), &calldata).unwrap();
+        assert_eq!(
+            labels(&result),
+            [
+                "offset of uint256[][] a → 0x40",
+                "offset of bytes b → 0x140",
+                "length of uint256[][] a: 2",
+                "offset of uint256[] a[0] → 0x40",
+                "offset of uint256[] a[1] → 0xa0",
+                "length of uint256[] a[0]: 2",
+                "uint256 a[0][0]",
+                "uint256 a[0][1]",
+                "length of uint256[] a[1]: 1",
+                "uint256 a[1][0]",
+                "length of bytes b: 5 bytes",
+                "bytes b data + padding",
+            ]
+        );
+        let sections: Vec<_> = result["layout"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["section"].as_str().unwrap())
+            .collect();
+        assert_eq!(sections[..3], ["head", "head", "tail"]);
+        assert_eq!(result["layout"][9]["param"], 0);
+        assert_eq!(result["layout"][11]["param"], 1);
+        assert!(result.get("error").is_none());
+    }
+
+    #[test]
+    fn out_of_bounds_offsets() {
+        let result = inspect_calldata(Some("(bytes)"), &words(&[0x1000])).unwrap();
+        assert_eq!(result["error"], "value at 0x0000 is out of bounds");
+        assert_eq!(labels(&result), ["unused"]);
+
+        let result = inspect_calldata(Some("(bytes)"), &words(&[0x20, 0x40, 1])).unwrap();
+        assert_eq!(result["error"], "bytes [0] is truncated");
+        assert_eq!(
+            labels(&result),
+            [
+                "offset of bytes [0] → 0x20",
+                "length of bytes [0]: 64 bytes",
+                "unused"
+            ]
+        );
+
+        let result = inspect_calldata(Some("(uint256[])"), &words(&[0x20, 5, 1])).unwrap();
+        assert_eq!(result["error"], "uint256[] [0] is truncated");
+
+        let result = inspect_calldata(Some("(bytes)"), &words(&[0x10])).unwrap();
+        assert_eq!(result["error"], "unaligned offset at 0x0000");
+
+        let result = inspect_calldata(Some("(uint256,uint256)"), &words(&[1])).unwrap();
+        assert_eq!(result["error"], "calldata is truncated at 0x0020");
+    }
+
+    #[test]
+    fn shared_tails_are_annotated_once() {
+        // three elements pointing to the same array
+        let calldata = words(&[0x20, 3, 0x60, 0x60, 0x60, 1, 7]);
+        let result = inspect_calldata(Some("(uint256[][])"), &calldata).unwrap();
+        assert_eq!(
+            labels(&result),
+            [
+                "offset of uint256[][] [0] → 0x20",
+                "length of uint256[][] [0]: 3",
+                "offset of uint256[] [0][0] → 0x60",
+                "offset of uint256[] [0][1] → 0x60",
+                "offset of uint256[] [0][2] → 0x60",
+                "length of uint256[] [0][0]: 1",
+                "uint256 [0][0][0]",
+            ]
+        );
+        assert!(result.get("error").is_none());
+    }
+
+    #[test]
+    fn overlapping_arrays_exhaust_the_budget() {
+        // 20 arrays of 20 elements each starting one word after the previous one
+        let (arrays, region) = (20, 40);
+        let mut values = vec![0x20, arrays];
+        values.extend((0..arrays).map(|i| 0x40 + (arrays + i) * 32));
+        values.extend(std::iter::repeat_n(arrays, region));
+        let result = inspect_calldata(Some("(uint256[][])"), &words(&values)).unwrap();
+        assert_eq!(result["error"], "too many overlapping offsets to annotate");
+        assert_eq!(result["layout"].as_array().unwrap().len(), values.len());
+    }
+}
@@ -0,0 +1,352 @@
+use super::opcodes::{immediate_size, is_terminator, opcode};
+
+use alloy_core::primitives::hex;
+use alloy_json_abi::JsonAbi;
+use serde_json::{json, Map, Value};
+use std::ops::Range;
+
+const CODECOPY: u8 = 0x39;
+const RETURN: u8 = 0xf3;
+const JUMPDEST: u8 = 0x5b;
+const PUSH4: u8 = 0x63;
+const EQ: u8 = 0x14;
+const JUMPI: u8 = 0x57;
+
+/// An instruction of the bytecode, at its program counter.
+#[derive(Debug, PartialEq)]
+pub struct Instruction<'a> {
+    pub pc: usize,
+    pub code: u8,
+    /// Immediate of `PUSH1`..`PUSH32`, truncated if the bytecode ends before it.
+    pub push: &'a [u8],
+}
+
+impl Instruction<'_> {
+    /// Whether the bytecode ends before the whole immediate of the instruction.
+    pub fn is_truncated(&self) -> bool {
+        self.push.len() < immediate_size(self.code)
+    }
+}
+
+/// Splits bytecode into its instructions.
+pub fn instructions(code: &[u8]) -> Vec<Instruction<'_>> {
+    let mut instructions = Vec::new();
+    let mut pc = 0;
+    while pc < code.len() {
+        let end = (pc + 1 + immediate_size(code[pc])).min(code.len());
+        instructions.push(Instruction {
+            pc,
+            code: code[pc],
+            push: &code[pc + 1..end],
+        });
+        pc = end;
+    }
+    instructions
+}
+
+/// Range of the CBOR metadata solc appends to the runtime code, including its 2-byte length.
+pub fn metadata_range(code: &[u8]) -> Option<Range<usize>> {
+    let len = code.len().checked_sub(2)?;
+    let cbor_len = u16::from_be_bytes([code[len], code[len + 1]]) as usize;
+    let start = len.checked_sub(cbor_len)?;
+    let cbor = &code[start..len];
+    // a CBOR map, keyed by `ipfs`, `bzzr0`, `bzzr1`, `solc` or `experimental`
+    let is_map = matches!(cbor.first(), Some(0xa1..=0xbf));
+    let has_key = [&b"ipfs"[..], b"bzzr", b"solc", b"experimental"]
+        .iter()
+        .any(|key| cbor.windows(key.len()).any(|w| w == *key));
+    (is_map && has_key).then_some(start..code.len())
+}
+
+/// Disassembles bytecode: the instructions of the creation and runtime code (when the
+/// creation code can be told apart by the `CODECOPY` of its runtime), the metadata trailer and
+/// the selectors of the dispatcher, resolved with the ABIs of the notebook.
+///
+/// Instructions are `{"pc", "op", "push"?}`, marked with `"block": true` when they start a
+/// new basic block and with `"truncated": true` when the code ends within their immediate.
+pub fn disassemble(code: &[u8], abis: &[JsonAbi]) -> Value {
+    let mut result = Map::new();
+    let mut sections = Vec::new();
+    let runtime = match runtime_range(code) {
+        Some(runtime) => {
+            sections.push(section("creation", 0, &code[..runtime.start]));
+            if runtime.end < code.len() {
+                // ie: the constructor arguments of a creation transaction
+                result.insert(
+                    "args".to_string(),
+                    json!(format!("0x{}", hex::encode(&code[runtime.end..]))),
+                );
+            }
+            runtime
+        }
+        None => 0..code.len(),
+    };
+    let runtime_code = &code[runtime.clone()];
+    let metadata = metadata_range(runtime_code);
+    let executable = &runtime_code[..metadata.as_ref().map_or(runtime_code.len(), |m| m.start)];
+    let name = if runtime.start > 0 { "runtime" } else { "code" };
+    sections.push(section(name, runtime.start, executable));
+
+    result.insert(
+        "selectors".to_string(),
+        Value::Array(dispatcher_selectors(executable, abis)),
+    );
+    result.insert("sections".to_string(), Value::Array(sections));
+    if let Some(metadata) = metadata {
+        result.insert(
+            "metadata".to_string(),
+            json!({
+                "offset": format!("0x{:04x}", runtime.start + metadata.start),
+                "length": metadata.len(),
+                "data": format!("0x{}", hex::encode(&runtime_code[metadata])),
+            }),
+        );
+    }
+    Value::Object(result)
+}
+
+fn section(name: &str, offset: usize, code: &[u8]) -> Value {
+    let mut block_start = false;
+    let instructions = instructions(code)
+        .iter()
+        .map(|instruction| {
+            let mut row = Map::new();
+            row.insert("pc".to_string(), json!(format!("0x{:04x}", instruction.pc)));
+            let op = match opcode(instruction.code) {
+                Some(op) => op.name.to_string(),
+                None => format!("UNKNOWN(0x{:02x})", instruction.code),
+            };
+            row.insert("op".to_string(), json!(op));
+            if !instruction.push.is_empty() {
+                row.insert(
+                    "push".to_string(),
+                    json!(format!("0x{}", hex::encode(instruction.push))),
+                );
+            }
+            if instruction.is_truncated() {
+                row.insert("truncated".to_string(), json!(true));
+            }
+            if instruction.pc > 0 && (block_start || instruction.code == JUMPDEST) {
+                row.insert("block".to_string(), json!(true));
+            }
+            block_start = is_terminator(instruction.code);
+            Value::Object(row)
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "name": name,
+        "offset": format!("0x{:04x}", offset),
+        "length": code.len(),
+        "instructions": instructions,
+    })
+}
+
+/// Finds the runtime code in creation code, from the constant `offset` and `size` of the last
+/// `CODECOPY` before the first `RETURN`. Stack values are only tracked while they're
+/// constants, which is enough for the `PUSH2 size DUP1 PUSH2 offset PUSH0 CODECOPY PUSH0
+/// RETURN` sequence compilers emit.
+fn runtime_range(code: &[u8]) -> Option<Range<usize>> {
+    let mut stack: Vec<Option<usize>> = Vec::new();
+    let mut copied = None;
+    for instruction in instructions(code) {
+        let op = opcode(instruction.code)?;
+        match instruction.code {
+            0x5f..=0x7f => stack.push(
+                // offsets and sizes, which fit in a wasm32 usize
+                (instruction.push.len() <= 4).then(|| {
+                    instruction
+                        .push
+                        .iter()
+                        .fold(0usize, |value, byte| value << 8 | *byte as usize)
+                }),
+            ),
+            // DUP1..DUP16
+            0x80..=0x8f => {
+                let depth = (instruction.code - 0x7f) as usize;
+                let value = stack.len().checked_sub(depth).and_then(|i| stack[i]);
+                stack.push(value);
+            }
+            // SWAP1..SWAP16
+            0x90..=0x9f => {
+                let depth = (instruction.code - 0x8f) as usize;
+                if let Some(i) = stack.len().checked_sub(depth + 1) {
+                    let top = stack.len() - 1;
+                    stack.swap(i, top);
+                }
+            }
+            CODECOPY => {
+                let _dest = stack.pop().flatten();
+                let offset = stack.pop().flatten();
+                let size = stack.pop().flatten();
+                if let (Some(offset), Some(size)) = (offset, size) {
+                    copied = offset.checked_add(size).map(|end| offset..end);
+                }
+            }
+            RETURN => {
+                return copied.filter(|r| r.start > 0 && r.start < r.end && r.end <= code.len());
+            }
+            _ => {
+                for _ in 0..op.inputs {
+                    stack.pop();
+                }
+                for _ in 0..op.outputs {
+                    stack.push(None);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Selectors compared against by the dispatcher, ie: `DUP1 PUSH4 0xa9059cbb EQ PUSH2 0x00a2
+/// JUMPI` (or with the `DUP` after the `PUSH4`), with the destination of their jump.
+fn dispatcher_selectors(code: &[u8], abis: &[JsonAbi]) -> Vec<Value> {
+    let instructions = instructions(code);
+    let mut selectors: Vec<Value> = Vec::new();
+    for (i, instruction) in instructions.iter().enumerate() {
+        if instruction.code != PUSH4 || instruction.push.len() != 4 {
+            continue;
+        }
+        let next = &instructions[i + 1..];
+        let next = match next.first() {
+            Some(dup) if (0x80..=0x8f).contains(&dup.code) => &next[1..],
+            _ => next,
+        };
+        let jumpdest = match next {
+            [eq, push, jumpi, ..]
+                if eq.code == EQ && (0x60..=0x7f).contains(&push.code) && jumpi.code == JUMPI =>
+            {
+                format!("0x{:0>4}", hex::encode(push.push))
+            }
+            _ => continue,
+        };
+        let selector = format!("0x{}", hex::encode(instruction.push));
+        if selectors.iter().any(|s| s["selector"] == selector) {
+            continue;
+        }
+        let mut row = Map::new();
+        row.insert("selector".to_string(), json!(selector));
+        let function = abis
+            .iter()
+            .flat_map(|abi| abi.functions())
+            .find(|f| f.selector().as_slice() == instruction.push);
+        match function {
+            Some(function) => {
+                row.insert("function".to_string(), json!(function.signature()));
+            }
+            None => {
+                let candidates = super::signatures::candidates(
+                    super::signatures::functions(instruction.push)
+                        .iter()
+                        .map(|f| f.signature()),
+                );
+                if !candidates.is_empty() {
+                    row.insert("candidates".to_string(), json!(candidates));
+                }
+            }
+        }
+        row.insert("jumpdest".to_string(), json!(jumpdest));
+        selectors.push(Value::Object(row));
+    }
+    selectors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// solc metadata: `{"ipfs": <34-byte multihash>, "solc": 0.8.24}` and its length.
+    fn metadata() -> Vec<u8> {
+        let mut cbor = hex::decode("a264697066735822").unwrap();
+        cbor.extend([0x12, 0x20]);
+        cbor.extend([0xab; 32]);
+        cbor.extend(hex::decode("64736f6c6343000818").unwrap());
+        cbor.extend((cbor.len() as u16).to_be_bytes());
+        cbor
+    }
+
+    #[test]
+    fn instructions_and_truncated_immediates() {
+        let code = hex::decode("6001615b").unwrap();
+        let instructions = instructions(&code);
+        assert_eq!(
+            instructions,
+            vec![
+                Instruction {
+                    pc: 0,
+                    code: 0x60,
+                    push: &[0x01],
+                },
+                Instruction {
+                    pc: 2,
+                    code: 0x61,
+                    push: &[0x5b],
+                },
+            ]
+        );
+        assert!(!instructions[0].is_truncated());
+        assert!(instructions[1].is_truncated());
+
+        let listing = disassemble(&[0x60], &[]);
+        assert_eq!(
+            listing["sections"][0]["instructions"],
+            json!([{ "pc": "0x0000", "op": "PUSH1", "truncated": true }])
+        );
+    }
+
+    #[test]
+    fn metadata_trailer() {
+        let mut code = hex::decode("6080604052").unwrap();
+        code.extend(metadata());
+        assert_eq!(metadata_range(&code), Some(5..code.len()));
+        assert_eq!(metadata_range(&hex::decode("6080604052").unwrap()), None);
+        assert_eq!(metadata_range(&[0x00]), None);
+
+        let listing = disassemble(&code, &[]);
+        assert_eq!(listing["metadata"]["offset"], "0x0005");
+        assert_eq!(listing["metadata"]["length"], 53);
+        assert_eq!(listing["sections"][0]["length"], 5);
+    }
+
+    #[test]
+    fn runtime_of_creation_code() {
+        // PUSH1 5 DUP1 PUSH1 9 PUSH0 CODECOPY PUSH0 RETURN, returning the 5 bytes at offset 9
+        let mut code = hex::decode("60058060095f395ff3").unwrap();
+        code.extend(hex::decode("602a5f5200").unwrap());
+        assert_eq!(runtime_range(&code), Some(9..14));
+        // the runtime code alone has no CODECOPY
+        assert_eq!(runtime_range(&code[9..]), None);
+
+        code.extend([0u8; 31]);
+        code.push(1);
+        let listing = disassemble(&code, &[]);
+        assert_eq!(listing["sections"][0]["name"], "creation");
+        assert_eq!(listing["sections"][1]["name"], "runtime");
+        assert_eq!(listing["sections"][1]["offset"], "0x0009");
+        assert_eq!(listing["args"], format!("0x{:064x}", 1));
+    }
+
+    #[test]
+    fn dispatcher() {
+        // DUP1 PUSH4 transfer EQ PUSH2 0x0020 JUMPI, then PUSH4 balanceOf DUP2 EQ PUSH2 0x0030 JUMPI
+        let code = hex::decode(concat!(
+            "8063a9059cbb1461002057",
+            "6370a0823181146100305700"
+        ))
+        .unwrap();
+        let abi = JsonAbi::parse(["function transfer(address to, uint256 amount)"]).unwrap();
+        let selectors = dispatcher_selectors(&code, &[abi]);
+        assert_eq!(selectors.len(), 2);
+        assert_eq!(
+            selectors[0],
+            json!({
+                "selector": "0xa9059cbb",
+                "function": "transfer(address,uint256)",
+                "jumpdest": "0x0020",
+            })
+        );
+        assert_eq!(selectors[1]["selector"], "0x70a08231");
+        assert_eq!(selectors[1]["candidates"][0], "balanceOf(address)");
+        assert_eq!(selectors[1]["jumpdest"], "0x0030");
+    }
+}
@@ -0,0 +1,460 @@
+use super::u256_to_address;
+use crate::parser::types::result::ParseResult;
+
+use alloy_core::primitives::{hex, Address, U256};
+use alloy_dyn_abi::eip712::TypedData;
+use serde_json::{json, Value};
+
+/// Canonical Permit2 deployment, the same on every chain.
+const PERMIT2: &str = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
+
+/// Computes the EIP-712 digest of an `eth_signTypedData_v4` payload, along with the
+/// intermediate values (encoded types, type hashes, domain separator and struct hash)
+/// needed to debug mismatching signatures.
+pub fn eip712_hash(typed_data: &Value) -> Result<Value, String> {
+    let typed_data: TypedData = serde_json::from_value(typed_data.clone())
+        .map_err(|e| format!("invalid typed data: {}", e))?;
+    let domain = &typed_data.domain;
+    let types = typed_data
+        .resolver
+        .linearize(&typed_data.primary_type)
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|def| {
+            let name = def.type_name();
+            Ok(json!({
+                "name": name,
+                "encodeType": typed_data.resolver.encode_type(name).map_err(|e| e.to_string())?,
+                "typeHash": typed_data.resolver.type_hash(name).map_err(|e| e.to_string())?.to_string(),
+            }))
+        })
+        .collect::<Result<Vec<Value>, String>>()?;
+
+    Ok(json!({
+        "domain": {
+            "encodeType": domain.encode_type(),
+            "typeHash": domain.type_hash().to_string(),
+            "separator": domain.separator().to_string(),
+        },
+        "primaryType": typed_data.primary_type,
+        "types": types,
+        "structHash": typed_data.hash_struct().map_err(|e| e.to_string())?.to_string(),
+        "digest": typed_data.eip712_signing_hash().map_err(|e| e.to_string())?.to_string(),
+    }))
+}
+
+/// EIP-2612 `permit` digest:
+/// `(token, name, version, chain_id, owner, spender, value, nonce, deadline)`.
+pub fn erc2612_permit_digest(args: &[ParseResult]) -> Result<Value, String> {
+    let [token, name, version, chain_id, owner, spender, value, nonce, deadline] = args else {
+        return Err("expected 9 arguments".to_string());
+    };
+    eip712_hash(&json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" },
+            ],
+            "Permit": [
+                { "name": "owner", "type": "address" },
+                { "name": "spender", "type": "address" },
+                { "name": "value", "type": "uint256" },
+                { "name": "nonce", "type": "uint256" },
+                { "name": "deadline", "type": "uint256" },
+            ],
+        },
+        "primaryType": "Permit",
+        "domain": {
+            "name": text(name)?,
+            "version": text(version)?,
+            "chainId": uint(chain_id)?,
+            "verifyingContract": address(token)?,
+        },
+        "message": {
+            "owner": address(owner)?,
+            "spender": address(spender)?,
+            "value": uint(value)?,
+            "nonce": uint(nonce)?,
+            "deadline": uint(deadline)?,
+        },
+    }))
+}
+
+fn permit2_domain(chain_id: &ParseResult) -> Result<Value, String> {
+    Ok(json!({
+        "name": "Permit2",
+        "chainId": uint(chain_id)?,
+        "verifyingContract": PERMIT2,
+    }))
+}
+
+const PERMIT2_DOMAIN_TYPE: &str = r#"[
+    { "name": "name", "type": "string" },
+    { "name": "chainId", "type": "uint256" },
+    { "name": "verifyingContract", "type": "address" }
+]"#;
+
+/// Permit2 `PermitSingle` (allowance) digest:
+/// `(chain_id, token, amount, expiration, nonce, spender, sig_deadline)`.
+pub fn permit2_single_digest(args: &[ParseResult]) -> Result<Value, String> {
+    let [chain_id, token, amount, expiration, nonce, spender, sig_deadline] = args else {
+        return Err("expected 7 arguments".to_string());
+    };
+    eip712_hash(&json!({
+        "types": {
+            "EIP712Domain": serde_json::from_str::<Value>(PERMIT2_DOMAIN_TYPE).unwrap(),
+            "PermitSingle": [
+                { "name": "details", "type": "PermitDetails" },
+                { "name": "spender", "type": "address" },
+                { "name": "sigDeadline", "type": "uint256" },
+            ],
+            "PermitDetails": [
+                { "name": "token", "type": "address" },
+                { "name": "amount", "type": "uint160" },
+                { "name": "expiration", "type": "uint48" },
+                { "name": "nonce", "type": "uint48" },
+            ],
+        },
+        "primaryType": "PermitSingle",
+        "domain": permit2_domain(chain_id)?,
+        "message": {
+            "details": {
+                "token": address(token)?,
+                "amount": uint(amount)?,
+                "expiration": uint(expiration)?,
+                "nonce": uint(nonce)?,
+            },
+            "spender": address(spender)?,
+            "sigDeadline": uint(sig_deadline)?,
+        },
+    }))
+}
+
+/// Permit2 `PermitTransferFrom` (signature transfer) digest:
+/// `(chain_id, token, amount, nonce, deadline, spender)`.
+pub fn permit2_transfer_digest(args: &[ParseResult]) -> Result<Value, String> {
+    let [chain_id, token, amount, nonce, deadline, spender] = args else {
+        return Err("expected 6 arguments".to_string());
+    };
+    eip712_hash(&json!({
+        "types": {
+            "EIP712Domain": serde_json::from_str::<Value>(PERMIT2_DOMAIN_TYPE).unwrap(),
+            "PermitTransferFrom": [
+                { "name": "permitted", "type": "TokenPermissions" },
+                { "name": "spender", "type": "address" },
+                { "name": "nonce", "type": "uint256" },
+                { "name": "deadline", "type": "uint256" },
+            ],
+            "TokenPermissions": [
+                { "name": "token", "type": "address" },
+                { "name": "amount", "type": "uint256" },
+            ],
+        },
+        "primaryType": "PermitTransferFrom",
+        "domain": permit2_domain(chain_id)?,
+        "message": {
+            "permitted": {
+                "token": address(token)?,
+                "amount": uint(amount)?,
+            },
+            "spender": address(spender)?,
+            "nonce": uint(nonce)?,
+            "deadline": uint(deadline)?,
+        },
+    }))
+}
+
+/// Safe (1.3.0+) transaction hash, either with every field:
+/// `(safe, chain_id, to, value, data, operation, safe_tx_gas, base_gas, gas_price, gas_token,
+/// refund_receiver, nonce)`, or without the gas refund fields, which default to zero:
+/// `(safe, chain_id, to, value, data, operation, nonce)`.
+pub fn safe_tx_hash(args: &[ParseResult]) -> Result<Value, String> {
+    let zero = ParseResult::Value(U256::ZERO);
+    let (safe, chain_id, to, value, data, operation, refund, nonce) = match args {
+        [safe, chain_id, to, value, data, operation, nonce] => (
+            safe,
+            chain_id,
+            to,
+            value,
+            data,
+            operation,
+            [&zero, &zero, &zero, &zero, &zero],
+            nonce,
+        ),
+        [safe, chain_id, to, value, data, operation, safe_tx_gas, base_gas, gas_price, gas_token, refund_receiver, nonce] => {
+            (
+                safe,
+                chain_id,
+                to,
+                value,
+                data,
+                operation,
+                [safe_tx_gas, base_gas, gas_price, gas_token, refund_receiver],
+                nonce,
+            )
+        }
+        _ => return Err("expected 7 or 12 arguments".to_string()),
+    };
+    let [safe_tx_gas, base_gas, gas_price, gas_token, refund_receiver] = refund;
+    eip712_hash(&json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" },
+            ],
+            "SafeTx": [
+                { "name": "to", "type": "address" },
+                { "name": "value", "type": "uint256" },
+                { "name": "data", "type": "bytes" },
+                { "name": "operation", "type": "uint8" },
+                { "name": "safeTxGas", "type": "uint256" },
+                { "name": "baseGas", "type": "uint256" },
+                { "name": "gasPrice", "type": "uint256" },
+                { "name": "gasToken", "type": "address" },
+                { "name": "refundReceiver", "type": "address" },
+                { "name": "nonce", "type": "uint256" },
+            ],
+        },
+        "primaryType": "SafeTx",
+        "domain": {
+            "chainId": uint(chain_id)?,
+            "verifyingContract": address(safe)?,
+        },
+        "message": {
+            "to": address(to)?,
+            "value": uint(value)?,
+            "data": bytes(data)?,
+            "operation": uint(operation)?,
+            "safeTxGas": uint(safe_tx_gas)?,
+            "baseGas": uint(base_gas)?,
+            "gasPrice": uint(gas_price)?,
+            "gasToken": address(gas_token)?,
+            "refundReceiver": address(refund_receiver)?,
+            "nonce": uint(nonce)?,
+        },
+    }))
+}
+
+fn uint(arg: &ParseResult) -> Result<String, String> {
+    match arg {
+        ParseResult::Value(u) => Ok(u.to_string()),
+        ParseResult::String(s) => s
+            .parse::<U256>()
+            .map(|u| u.to_string())
+            .map_err(|e| e.to_string()),
+        _ => Err(format!("expected a number, found {}", arg.to_string())),
+    }
+}
+
+fn address(arg: &ParseResult) -> Result<String, String> {
+    match arg {
+        ParseResult::Address(a) => Ok(a.to_checksum(None)),
+        ParseResult::Value(u) => Ok(u256_to_address(*u).to_checksum(None)),
+        ParseResult::String(s) => s
+            .parse::<Address>()
+            .map(|a| a.to_checksum(None))
+            .map_err(|e| e.to_string()),
+        _ => Err(format!("expected an address, found {}", arg.to_string())),
+    }
+}
+
+fn text(arg: &ParseResult) -> Result<String, String> {
+    match arg {
+        ParseResult::String(s) => Ok(s.to_string()),
+        ParseResult::Value(u) => Ok(u.to_string()),
+        _ => Err(format!("expected a string, found {}", arg.to_string())),
+    }
+}
+
+fn bytes(arg: &ParseResult) -> Result<String, String> {
+    arg.get_bytes().map(|b| format!("0x{}", hex::encode(b)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `Mail` example of EIP-712.
+    fn mail() -> Value {
+        json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" },
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" },
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" },
+                ],
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+            },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!",
+            },
+        })
+    }
+
+    #[test]
+    fn eip712_mail_example() {
+        let hash = eip712_hash(&mail()).unwrap();
+        assert_eq!(
+            hash["domain"]["separator"],
+            "0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            hash["types"][0]["encodeType"],
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            hash["types"][0]["typeHash"],
+            "0xa0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"
+        );
+        assert_eq!(
+            hash["structHash"],
+            "0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            hash["digest"],
+            "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    fn addr(s: &str) -> ParseResult {
+        s.parse::<Address>().unwrap().into()
+    }
+
+    fn num(n: u64) -> ParseResult {
+        U256::from(n).into()
+    }
+
+    #[test]
+    fn permit_matches_usdc() {
+        let owner = addr("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let args = [
+            addr("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            "USD Coin".into(),
+            "2".into(),
+            num(1),
+            owner.clone(),
+            owner,
+            num(1),
+            num(0),
+            num(0),
+        ];
+        let hash = erc2612_permit_digest(&args).unwrap();
+        // `DOMAIN_SEPARATOR()` and `PERMIT_TYPEHASH()` of USDC on mainnet
+        assert_eq!(
+            hash["domain"]["separator"],
+            "0x06c37168a7db5138defc7866392bb87a741f9b3d104deb5094588ce041cae335"
+        );
+        assert_eq!(
+            hash["types"][0]["typeHash"],
+            "0x6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9"
+        );
+    }
+
+    #[test]
+    fn permit2_type_hashes() {
+        let token = addr("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let spender = addr("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let single = [
+            num(1),
+            token.clone(),
+            num(1),
+            num(0),
+            num(0),
+            spender.clone(),
+            num(0),
+        ];
+        let single = permit2_single_digest(&single).unwrap();
+        let transfer = [num(1), token, num(1), num(0), num(0), spender];
+        let transfer = permit2_transfer_digest(&transfer).unwrap();
+        // `DOMAIN_SEPARATOR()` of Permit2 on mainnet
+        for hash in [&single, &transfer] {
+            assert_eq!(
+                hash["domain"]["separator"],
+                "0x866a5aba21966af95d6c7ab78eb2b2fc913915c28be3b9aa07cc04ff903e3f28"
+            );
+        }
+        // `_PERMIT_SINGLE_TYPEHASH`, `_PERMIT_DETAILS_TYPEHASH`, `_PERMIT_TRANSFER_FROM_TYPEHASH`
+        // and `_TOKEN_PERMISSIONS_TYPEHASH` of Permit2
+        assert_eq!(
+            single["types"][0]["typeHash"],
+            "0xf3841cd1ff0085026a6327b620b67997ce40f282c88a8e905a7a5626e310f3d0"
+        );
+        assert_eq!(
+            single["types"][1]["typeHash"],
+            "0x65626cad6cb96493bf6f5ebea28756c966f023ab9e8a83a7101849d5573b3678"
+        );
+        assert_eq!(
+            transfer["types"][0]["typeHash"],
+            "0x939c21a48a8dbe3a9a2404a1d46691e4d39f6583d6ec6b35714604c986d80106"
+        );
+        assert_eq!(
+            transfer["types"][1]["typeHash"],
+            "0x618358ac3db8dc274f0cd8829da7e234bd48cd73c4a740aede1adec9846d06a1"
+        );
+    }
+
+    #[test]
+    fn safe_tx_type_hashes() {
+        let safe = addr("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let args = [
+            safe.clone(),
+            num(1),
+            safe,
+            num(0),
+            "0x".into(),
+            num(0),
+            num(0),
+        ];
+        let hash = safe_tx_hash(&args).unwrap();
+        // `DOMAIN_SEPARATOR_TYPEHASH` and `SAFE_TX_TYPEHASH` of the Safe contracts
+        assert_eq!(
+            hash["domain"]["typeHash"],
+            "0x47e79534a245952e8b16893a336b85a3d9ea9fa8c573f3d803afb92a79469218"
+        );
+        assert_eq!(
+            hash["types"][0]["typeHash"],
+            "0xbb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8"
+        );
+        // the refund fields default to zero
+        let zero = num(0);
+        let full = [
+            args[0].clone(),
+            num(1),
+            args[2].clone(),
+            num(0),
+            "0x".into(),
+            num(0),
+            num(0),
+            num(0),
+            num(0),
+            zero.clone(),
+            zero,
+            num(0),
+        ];
+        assert_eq!(safe_tx_hash(&full).unwrap(), hash);
+        // numbers don't keep the leading zeros of bytes
+        let mut with_data = args.clone();
+        with_data[4] = num(1);
+        assert!(safe_tx_hash(&with_data).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use alloy_core::primitives::{hex, keccak256, B256};
+
+/// Normalizes an ASCII ENS name: lowercases it and validates its labels. Other characters are
+/// rejected, as mapping them (ie: fullwidth letters, emoji, confusables) needs the ENSIP-15
+/// tables, which aren't bundled; the name has to be normalized beforehand.
+pub fn normalize(name: &str) -> Result<String, String> {
+    if let Some(c) = name.chars().find(|c| !c.is_ascii()) {
+        return Err(format!(
+            "non-ASCII character {:?}: only ASCII names are supported",
+            c
+        ));
+    }
+    let name = name.to_lowercase();
+    if name.is_empty() {
+        return Ok(name);
+    }
+    for label in name.split('.') {
+        validate_label(label)?;
+    }
+    Ok(name)
+}
+
+fn validate_label(label: &str) -> Result<(), String> {
+    if label.is_empty() {
+        return Err("empty label".to_string());
+    }
+    // already hashed labels, ie: `[4f5b812789fc606be1b3b16908db13fc7a9adf7ca72641f84d75b47069d3d7f0]`
+    if is_encoded_label(label) {
+        return Ok(());
+    }
+    let chars: Vec<char> = label.chars().collect();
+    if let Some(pos) = chars.iter().rposition(|c| *c == '_') {
+        if chars[..pos].iter().any(|c| *c != '_') {
+            return Err(format!("underscore allowed only at the start: {}", label));
+        }
+    }
+    if chars.len() >= 4 && chars[2] == '-' && chars[3] == '-' {
+        return Err(format!("invalid label extension: {}", label));
+    }
+    for c in chars {
+        if !(c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_' || c == '$') {
+            return Err(format!("disallowed character {:?} in label: {}", c, label));
+        }
+    }
+    Ok(())
+}
+
+fn is_encoded_label(label: &str) -> bool {
+    label.len() == 66
+        && label.starts_with('[')
+        && label.ends_with(']')
+        && label[1..65].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Computes the ENS labelhash of a single label (after normalization).
+pub fn labelhash(label: &str) -> Result<B256, String> {
+    let label = normalize(label)?;
+    if label.contains('.') {
+        return Err("labelhash expects a single label".to_string());
+    }
+    Ok(hash_label(&label))
+}
+
+fn hash_label(label: &str) -> B256 {
+    if is_encoded_label(label) {
+        match hex::decode(&label[1..65]) {
+            Ok(bytes) => B256::from_slice(&bytes),
+            Err(_) => keccak256(label),
+        }
+    } else {
+        keccak256(label)
+    }
+}
+
+/// Computes the ENS namehash of a name (after normalization), as defined in EIP-137.
+pub fn namehash(name: &str) -> Result<B256, String> {
+    let name = normalize(name)?;
+    let mut node = B256::ZERO;
+    if name.is_empty() {
+        return Ok(node);
+    }
+    for label in name.rsplit('.') {
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(node.as_slice());
+        buf[32..].copy_from_slice(hash_label(label).as_slice());
+        node = keccak256(buf);
+    }
+    Ok(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_core::primitives::b256;
+
+    #[test]
+    fn namehash_vectors() {
+        assert_eq!(namehash("").unwrap(), B256::ZERO);
+        assert_eq!(
+            namehash("eth").unwrap(),
+            b256!("93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae")
+        );
+        assert_eq!(
+            namehash("vitalik.eth").unwrap(),
+            b256!("ee6c4522aab0003e8d14cd40a6af439055fd2577951148c14b6cea9a53475835")
+        );
+        assert_eq!(namehash("Vitalik.ETH"), namehash("vitalik.eth"));
+    }
+
+    #[test]
+    fn labelhash_vectors() {
+        assert_eq!(
+            labelhash("eth").unwrap(),
+            b256!("4f5b812789fc606be1b3b16908db13fc7a9adf7ca72641f84d75b47069d3d7f0")
+        );
+        assert_eq!(
+            labelhash("vitalik").unwrap(),
+            b256!("af2caa1c2ca1d027f1ac823b529d0a67cd144264b2789fa2ea4d63a67c7103cc")
+        );
+        assert!(labelhash("vitalik.eth").is_err());
+    }
+
+    #[test]
+    fn encoded_labels() {
+        let encoded = "[af2caa1c2ca1d027f1ac823b529d0a67cd144264b2789fa2ea4d63a67c7103cc]";
+        assert_eq!(labelhash(encoded), labelhash("vitalik"));
+        assert_eq!(
+            namehash(&format!("{}.eth", encoded)),
+            namehash("vitalik.eth")
+        );
+    }
+
+    #[test]
+    fn rejected_names() {
+        for name in [
+            "ｖｉｔａｌｉｋ.eth",
+            "vitalik.eth\u{200d}",
+            "vitalik..eth",
+            "vitalik.eth.",
+            "vit alik.eth",
+            "a_b.eth",
+            "ab--c.eth",
+        ] {
+            assert!(namehash(name).is_err(), "{}", name);
+        }
+        assert!(namehash("__ab.eth").is_ok());
+    }
+}
@@ -0,0 +1,360 @@
+use super::{opcodes::opcode, revert, u256_to_address};
+use crate::parser::types::result::ParseResult;
+
+use alloy_core::primitives::{hex, Address, U256};
+use revm::{
+    db::{CacheDB, EmptyDB},
+    inspector_handle_register,
+    interpreter::Interpreter,
+    primitives::{self, AccountInfo, Bytecode, ExecutionResult, Output, SpecId, TxKind},
+    Database, Evm, EvmContext, Inspector,
+};
+use serde_json::{json, Map, Value};
+use std::str::FromStr;
+
+/// Default address of the executed code, the first contract deployed by Anvil and Hardhat.
+const CONTRACT: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+/// Default caller, the first Anvil and Hardhat account.
+const CALLER: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+/// Gas available to an execution (or a precompile call), as in a mainnet block.
+pub const GAS_LIMIT: u64 = 30_000_000;
+/// Steps recorded in the trace, which is truncated past that.
+const MAX_STEPS: usize = 5_000;
+
+/// Records the state of the interpreter before each instruction.
+#[derive(Default)]
+struct Tracer {
+    steps: Vec<Value>,
+    truncated: bool,
+}
+
+impl<DB: Database> Inspector<DB> for Tracer {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        if self.steps.len() == MAX_STEPS {
+            self.truncated = true;
+            return;
+        }
+        let code = interp.current_opcode();
+        let op = match opcode(code) {
+            Some(op) => op.name.to_string(),
+            None => format!("UNKNOWN(0x{:02x})", code),
+        };
+        let stack: Vec<Value> = interp
+            .stack
+            .data()
+            .iter()
+            .rev()
+            .map(|word| json!(format!("{:#x}", word)))
+            .collect();
+        self.steps.push(json!({
+            "pc": interp.program_counter(),
+            "op": op,
+            "gas": interp.gas.remaining(),
+            "depth": context.journaled_state.depth(),
+            "stack": stack,
+        }));
+    }
+}
+
+/// Runs code against an in-memory EVM (Cancun), as a call from `caller` to an account holding
+/// the code. Takes named fields: `code`, `calldata`, `caller`, `address`, `value`, `gas` and
+/// a `storage` prestate, either a json object or a list of `(slot, value)`.
+///
+/// Returns the output (or decoded revert data), the gas used by the transaction (including
+/// the 21000 intrinsic gas), the logs, the storage writes and a step trace.
+pub fn execute(fields: &[(String, ParseResult)]) -> Result<Value, String> {
+    let mut code = None;
+    let mut calldata = Vec::new();
+    let mut caller = Address::from_str(CALLER).unwrap();
+    let mut address = Address::from_str(CONTRACT).unwrap();
+    let mut value = U256::ZERO;
+    let mut gas = GAS_LIMIT;
+    let mut storage = Vec::new();
+    for (name, arg) in fields {
+        match name.as_str() {
+            "code" | "bytecode" => code = Some(to_bytes(name, arg)?),
+            "calldata" | "data" | "input" => calldata = to_bytes(name, arg)?,
+            "caller" | "from" => caller = to_address(name, arg)?,
+            "address" | "to" => address = to_address(name, arg)?,
+            "value" => value = to_uint(name, arg)?,
+            "gas" | "gas_limit" => {
+                gas = u64::try_from(to_uint(name, arg)?).map_err(|e| format!("gas: {}", e))?
+            }
+            "storage" => storage = to_storage(arg)?,
+            _ => return Err(format!("unknown field: {}", name)),
+        }
+    }
+    let code = code.ok_or("code is required")?;
+    let (caller, address) = (to_revm_address(caller), to_revm_address(address));
+
+    let mut db = CacheDB::new(EmptyDB::default());
+    let code = Bytecode::new_raw(code.into());
+    db.insert_account_info(
+        address,
+        AccountInfo::new(primitives::U256::ZERO, 1, code.hash_slow(), code),
+    );
+    for (slot, value) in storage {
+        db.insert_account_storage(address, to_revm_uint(slot), to_revm_uint(value))
+            .map_err(|e| format!("storage: {:?}", e))?;
+    }
+    // the caller only needs to afford the value, as gas is free
+    db.insert_account_info(
+        caller,
+        AccountInfo {
+            balance: to_revm_uint(value),
+            ..Default::default()
+        },
+    );
+
+    let mut tracer = Tracer::default();
+    let result = {
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(&mut tracer)
+            .with_spec_id(SpecId::CANCUN)
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(address);
+                tx.data = calldata.into();
+                tx.value = to_revm_uint(value);
+                tx.gas_limit = gas;
+                tx.gas_price = primitives::U256::ZERO;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().map_err(|e| format!("{:?}", e))?
+    };
+
+    let mut output = Map::new();
+    output.insert("success".to_string(), json!(result.result.is_success()));
+    match &result.result {
+        ExecutionResult::Success {
+            output: Output::Call(data) | Output::Create(data, _),
+            ..
+        } => {
+            output.insert("output".to_string(), json!(hex_string(data)));
+        }
+        ExecutionResult::Revert { output: data, .. } => {
+            output.insert("revert".to_string(), json!(hex_string(data)));
+            if let Ok(error) = revert::decode_revert(data, None) {
+                output.insert("error".to_string(), error);
+            }
+        }
+        ExecutionResult::Halt { reason, .. } => {
+            output.insert("halt".to_string(), json!(format!("{:?}", reason)));
+        }
+    }
+    output.insert("gasUsed".to_string(), json!(result.result.gas_used()));
+    let logs: Vec<Value> = result
+        .result
+        .logs()
+        .iter()
+        .map(|log| {
+            json!({
+                "address": to_address_string(log.address),
+                "topics": log.data.topics().iter().map(|t| t.to_string()).collect::<Vec<_>>(),
+                "data": hex_string(&log.data.data),
+            })
+        })
+        .collect();
+    output.insert("logs".to_string(), json!(logs));
+    let mut writes = Vec::new();
+    let mut accounts: Vec<_> = result.state.iter().collect();
+    accounts.sort_by_key(|(account, _)| **account);
+    for (account, state) in accounts {
+        let mut slots: Vec<_> = state
+            .storage
+            .iter()
+            .filter(|(_, slot)| slot.is_changed())
+            .collect();
+        slots.sort_by_key(|(slot, _)| **slot);
+        for (slot, value) in slots {
+            writes.push(json!({
+                "address": to_address_string(*account),
+                "slot": format!("{:#x}", slot),
+                "from": format!("{:#x}", value.original_value),
+                "to": format!("{:#x}", value.present_value),
+            }));
+        }
+    }
+    output.insert("storage".to_string(), json!(writes));
+    output.insert("trace".to_string(), json!(tracer.steps));
+    if tracer.truncated {
+        output.insert("truncated".to_string(), json!(true));
+    }
+    Ok(Value::Object(output))
+}
+
+fn to_bytes(name: &str, arg: &ParseResult) -> Result<Vec<u8>, String> {
+    arg.get_bytes().map_err(|e| format!("{}: {}", name, e))
+}
+
+fn to_uint(name: &str, arg: &ParseResult) -> Result<U256, String> {
+    match arg {
+        ParseResult::Value(u) => Ok(*u),
+        ParseResult::String(s) => s.parse::<U256>().map_err(|e| format!("{}: {}", name, e)),
+        _ => Err(format!("{}: expected a number", name)),
+    }
+}
+
+fn to_address(name: &str, arg: &ParseResult) -> Result<Address, String> {
+    match arg {
+        ParseResult::Address(a) => Ok(*a),
+        ParseResult::Value(u) => Ok(u256_to_address(*u)),
+        ParseResult::String(s) => s.parse::<Address>().map_err(|e| format!("{}: {}", name, e)),
+        _ => Err(format!("{}: expected an address", name)),
+    }
+}
+
+/// Storage prestate, as `{"0x0": "0x2a"}` or `[(0, 42)]`.
+fn to_storage(arg: &ParseResult) -> Result<Vec<(U256, U256)>, String> {
+    let json_uint = |value: &Value| match value {
+        Value::String(s) => s.parse::<U256>().map_err(|e| format!("storage: {}", e)),
+        Value::Number(n) => n.to_string().parse::<U256>().map_err(|e| e.to_string()),
+        _ => Err("storage: expected numbers".to_string()),
+    };
+    match arg {
+        ParseResult::Json(Value::Object(slots)) => slots
+            .iter()
+            .map(|(slot, value)| Ok((json_uint(&json!(slot))?, json_uint(value)?)))
+            .collect(),
+        ParseResult::Array(slots) => slots
+            .iter()
+            .map(|slot| match slot {
+                ParseResult::Array(pair) if pair.len() == 2 => {
+                    Ok((to_uint("slot", &pair[0])?, to_uint("value", &pair[1])?))
+                }
+                _ => Err("storage: expected (slot, value) pairs".to_string()),
+            })
+            .collect(),
+        _ => Err("storage: expected a json object or (slot, value) pairs".to_string()),
+    }
+}
+
+fn to_revm_uint(u: U256) -> primitives::U256 {
+    primitives::U256::from_be_bytes(u.to_be_bytes::<32>())
+}
+
+fn to_revm_address(a: Address) -> primitives::Address {
+    primitives::Address::from(a.0 .0)
+}
+
+fn to_address_string(a: primitives::Address) -> String {
+    Address::from(a.0 .0).to_checksum(None)
+}
+
+fn hex_string(data: &[u8]) -> String {
+    format!("0x{}", hex::encode(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(code: &str, extra: Vec<(&str, ParseResult)>) -> Value {
+        let mut fields = vec![("code".to_string(), ParseResult::String(code.to_string()))];
+        fields.extend(extra.into_iter().map(|(name, arg)| (name.to_string(), arg)));
+        execute(&fields).unwrap()
+    }
+
+    fn word(n: u64) -> String {
+        format!("0x{:064x}", n)
+    }
+
+    #[test]
+    fn return_a_word() {
+        // PUSH1 42, PUSH0, MSTORE, PUSH1 32, PUSH0, RETURN
+        let result = run("0x602a5f5260205ff3", vec![]);
+        assert_eq!(result["success"], true);
+        assert_eq!(result["output"], word(42));
+        // intrinsic gas, three pushes and two PUSH0, MSTORE with a word of memory
+        assert_eq!(result["gasUsed"], 21_000 + 3 * 2 + 2 * 2 + 6);
+        let ops: Vec<_> = result["trace"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|step| step["op"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            ops,
+            ["PUSH1", "PUSH0", "MSTORE", "PUSH1", "PUSH0", "RETURN"]
+        );
+        assert_eq!(result["trace"][2]["stack"], json!(["0x0", "0x2a"]));
+        assert!(result.get("truncated").is_none());
+    }
+
+    #[test]
+    fn sstore_over_a_prestate() {
+        // PUSH1 42, PUSH0, SSTORE, STOP
+        let storage = ParseResult::Json(json!({ "0x0": "0x1" }));
+        let result = run("0x602a5f5500", vec![("storage", storage)]);
+        assert_eq!(result["success"], true);
+        assert_eq!(
+            result["storage"],
+            json!([{ "address": CONTRACT, "slot": "0x0", "from": "0x1", "to": "0x2a" }])
+        );
+
+        let storage = ParseResult::Array(vec![ParseResult::Array(vec![
+            ParseResult::Value(U256::ZERO),
+            ParseResult::Value(U256::from(42)),
+        ])]);
+        let result = run("0x602a5f5500", vec![("storage", storage)]);
+        assert_eq!(result["storage"], json!([]));
+    }
+
+    #[test]
+    fn log1() {
+        // PUSH1 42, PUSH0, MSTORE, PUSH1 0xff, PUSH1 32, PUSH0, LOG1, STOP
+        let result = run("0x602a5f5260ff60205fa100", vec![]);
+        assert_eq!(
+            result["logs"],
+            json!([{ "address": CONTRACT, "topics": [word(0xff)], "data": word(42) }])
+        );
+    }
+
+    #[test]
+    fn revert_with_a_panic() {
+        // Panic(1): PUSH4 selector, PUSH1 224, SHL, PUSH0, MSTORE, PUSH1 1, PUSH1 4, MSTORE,
+        // PUSH1 36, PUSH0, REVERT
+        let result = run("0x634e487b7160e01b5f52600160045260245ffd", vec![]);
+        assert_eq!(result["success"], false);
+        assert_eq!(result["revert"], format!("0x4e487b71{}", &word(1)[2..]));
+        assert_eq!(result["error"]["error"], "Panic(uint256)");
+        assert_eq!(result["error"]["code"], "0x01");
+        assert!(result.get("output").is_none());
+    }
+
+    #[test]
+    fn infinite_loop_runs_out_of_gas() {
+        // JUMPDEST, PUSH0, JUMP
+        let result = run(
+            "0x5b5f56",
+            vec![("gas", ParseResult::Value(U256::from(100_000)))],
+        );
+        assert_eq!(result["success"], false);
+        assert_eq!(result["halt"], "OutOfGas(Basic)");
+        assert_eq!(result["gasUsed"], 100_000);
+        assert_eq!(result["trace"].as_array().unwrap().len(), MAX_STEPS);
+        assert_eq!(result["truncated"], true);
+    }
+
+    #[test]
+    fn invalid_fields() {
+        assert_eq!(execute(&[]).unwrap_err(), "code is required");
+        let code = ("code".to_string(), ParseResult::String("0x00".to_string()));
+        let fields = [
+            code.clone(),
+            ("nonce".to_string(), ParseResult::Value(U256::ZERO)),
+        ];
+        assert_eq!(execute(&fields).unwrap_err(), "unknown field: nonce");
+        let fields = [
+            code,
+            ("storage".to_string(), ParseResult::Value(U256::ZERO)),
+        ];
+        assert!(execute(&fields).is_err());
+        let fields = [("code".to_string(), ParseResult::Value(U256::from(1)))];
+        assert!(execute(&fields)
+            .unwrap_err()
+            .starts_with("code: expected hex bytes"));
+    }
+}
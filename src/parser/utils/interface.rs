@@ -0,0 +1,394 @@
+use crate::parser::types::{abi::abi_encode_values, result::ParseResult};
+
+use std::collections::HashMap;
+
+use alloy_dyn_abi::DynSolType;
+use alloy_json_abi::{AbiItem, JsonAbi, Param};
+use serde_json::{json, Value};
+
+const ITEM_KEYWORDS: [&str; 8] = [
+    "function",
+    "event",
+    "error",
+    "constructor",
+    "fallback",
+    "receive",
+    "struct",
+    "enum",
+];
+
+/// Parses a pasted Solidity interface (`interface IERC20 { function transfer(address, uint256)
+/// external returns (bool); .. }`) or a list of human-readable ABI items, one per line. Returns
+/// `None` when the input looks like neither.
+///
+/// Interfaces evaluate to `{"interface": name, "abi": [..]}`, so blocks can refer to them by
+/// name; item lists evaluate to the JSON ABI. Structs, enums and user-defined value types are
+/// resolved to their ABI types, and contract types to `address`.
+pub fn parse_interface(input: &str) -> Option<Result<Value, String>> {
+    let input = strip_comments(input);
+    let mut input = input.trim();
+    while input.starts_with("pragma ") || input.starts_with("import ") {
+        input = input.split_once(';')?.1.trim_start();
+    }
+    let (name, items) = match input.strip_prefix("interface") {
+        Some(rest) if rest.starts_with(char::is_whitespace) => {
+            // ie: `interface IERC721 is IERC165 {`
+            let (name, body) = rest.split_once('{')?;
+            let body = body.trim_end().strip_suffix('}')?;
+            (
+                name.split_whitespace().next().map(String::from),
+                split_items(body),
+            )
+        }
+        _ => {
+            let first = input
+                .split(|c: char| c.is_whitespace() || c == '(')
+                .next()?;
+            if !ITEM_KEYWORDS.contains(&first) {
+                return None;
+            }
+            let items = input.lines().flat_map(split_items).collect::<Vec<String>>();
+            (None, items)
+        }
+    };
+    Some(build_abi(&items).map(|abi| {
+        let abi = serde_json::to_value(abi).unwrap_or_default();
+        match name {
+            Some(name) => json!({ "interface": name, "abi": abi }),
+            None => abi,
+        }
+    }))
+}
+
+fn build_abi(items: &[String]) -> Result<JsonAbi, String> {
+    // enums and user-defined value types are known upfront, while struct fields can only use
+    // the structs declared before them
+    let mut types: HashMap<String, String> = HashMap::new();
+    let mut structs: Vec<(String, Vec<String>)> = Vec::new();
+    for item in items {
+        let words: Vec<&str> = item.split_whitespace().collect();
+        match words.as_slice() {
+            ["enum", name, ..] => {
+                types.insert(name.trim_end_matches('{').to_string(), "uint8".to_string());
+            }
+            ["type", name, "is", ty] => {
+                types.insert(name.to_string(), ty.to_string());
+            }
+            _ => {}
+        }
+    }
+    for item in items.iter().filter(|item| item.starts_with("struct")) {
+        let (head, body) = item
+            .split_once('{')
+            .ok_or(format!("invalid struct: {}", item))?;
+        let struct_name = head.trim_start_matches("struct").trim().to_string();
+        let (field_types, field_names): (Vec<String>, Vec<String>) = body
+            .trim_end_matches('}')
+            .split(';')
+            .filter(|field| !field.trim().is_empty())
+            .map(|field| {
+                let field = resolve_types(field.trim(), &types);
+                let (mut ty, name) = match field.rsplit_once(char::is_whitespace) {
+                    Some((ty, name)) => (ty.trim().to_string(), name.to_string()),
+                    None => (field, String::new()),
+                };
+                if !ty.starts_with('(') {
+                    address_type(&mut ty);
+                }
+                (ty, name)
+            })
+            .unzip();
+        let tuple = format!("({})", field_types.join(","));
+        let canonical =
+            DynSolType::parse(&tuple).map_or(tuple.clone(), |ty| ty.sol_type_name().to_string());
+        types.insert(struct_name, tuple);
+        structs.push((canonical, field_names));
+    }
+
+    let mut abi = JsonAbi::new();
+    for item in items {
+        let keyword = item.split(|c: char| c.is_whitespace() || c == '(').next();
+        if !matches!(
+            keyword,
+            Some("function" | "event" | "error" | "constructor")
+        ) {
+            continue;
+        }
+        let item = resolve_types(item, &types);
+        match AbiItem::parse(&item).map_err(|e| format!("invalid item `{}`: {}", item, e))? {
+            AbiItem::Function(f) => {
+                let mut f = f.into_owned();
+                name_components(&mut f.inputs, &structs);
+                name_components(&mut f.outputs, &structs);
+                abi.functions.entry(f.name.clone()).or_default().push(f);
+            }
+            AbiItem::Event(e) => {
+                let mut e = e.into_owned();
+                for param in e.inputs.iter_mut() {
+                    address_type(&mut param.ty);
+                }
+                abi.events.entry(e.name.clone()).or_default().push(e);
+            }
+            AbiItem::Error(e) => {
+                let mut e = e.into_owned();
+                name_components(&mut e.inputs, &structs);
+                abi.errors.entry(e.name.clone()).or_default().push(e);
+            }
+            AbiItem::Constructor(c) => {
+                let mut c = c.into_owned();
+                name_components(&mut c.inputs, &structs);
+                abi.constructor = Some(c);
+            }
+            _ => {}
+        }
+    }
+    Ok(abi)
+}
+
+/// Splits declarations on `;`, keeping struct and enum bodies whole.
+fn split_items(body: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for c in body.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => {}
+        }
+        if c == ';' && depth == 0 {
+            items.push(current.trim().to_string());
+            current.clear();
+            continue;
+        }
+        current.push(c);
+        if c == '}' && depth == 0 {
+            items.push(current.trim().to_string());
+            current.clear();
+        }
+    }
+    items.push(current.trim().to_string());
+    items
+        .into_iter()
+        .map(|item| item.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Replaces the user-defined type names (possibly qualified, ie: `IPool.Order`) by their ABI
+/// types.
+fn resolve_types(item: &str, types: &HashMap<String, String>) -> String {
+    let mut resolved = String::new();
+    let mut word = String::new();
+    for c in item.chars().chain(std::iter::once(' ')) {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '$' || c == '.' {
+            word.push(c);
+            continue;
+        }
+        let name = word.rsplit('.').next().unwrap_or_default();
+        match types.get(name) {
+            Some(ty) => resolved.push_str(ty),
+            None => resolved.push_str(&word),
+        }
+        word.clear();
+        resolved.push(c);
+    }
+    resolved.pop();
+    resolved
+}
+
+/// Names the components of the struct parameters after the struct fields, as the
+/// human-readable format only knows about tuples.
+fn name_components(params: &mut [Param], structs: &[(String, Vec<String>)]) {
+    for param in params.iter_mut() {
+        address_type(&mut param.ty);
+    }
+    for param in params.iter_mut().filter(|p| !p.components.is_empty()) {
+        let ty = param.selector_type();
+        let tuple = ty.trim_end_matches(|c: char| c == ']' || c == '[' || c.is_ascii_digit());
+        if let Some((_, names)) = structs.iter().find(|(t, _)| t == tuple) {
+            for (component, name) in param.components.iter_mut().zip(names) {
+                component.name = name.clone();
+            }
+        }
+        name_components(&mut param.components, structs);
+    }
+}
+
+/// Types left unresolved are contracts declared elsewhere (ie: `IERC20 token`).
+fn address_type(ty: &mut String) {
+    if !ty.starts_with("tuple") && DynSolType::parse(ty).is_err() {
+        let suffix = ty.find('[').map_or("", |i| &ty[i..]);
+        *ty = format!("address{}", suffix);
+    }
+}
+
+fn strip_comments(input: &str) -> String {
+    let mut stripped = String::new();
+    let mut rest = input;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("//") {
+            rest = after.find('\n').map_or("", |i| &after[i..]);
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after.find("*/").map_or("", |i| &after[i + 2..]);
+        } else {
+            let c = rest.chars().next().unwrap();
+            stripped.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    stripped
+}
+
+/// Signature of a function, event or error of the ABI: canonical for functions and errors (ie:
+/// for `selector` or `abi_encode`), with the indexed parameters for events (ie: for
+/// `decode_log`).
+pub fn item_signature(abi: &JsonAbi, name: &str) -> Result<String, String> {
+    if let Some(function) = abi.function(name).and_then(|f| f.first()) {
+        return Ok(function.signature());
+    }
+    if let Some(event) = abi.event(name).and_then(|e| e.first()) {
+        return Ok(event.full_signature());
+    }
+    if let Some(error) = abi.error(name).and_then(|e| e.first()) {
+        return Ok(error.signature());
+    }
+    Err(format!("no function, event or error named {}", name))
+}
+
+/// Calldata of a call to a function of the ABI. Overloads are picked by their number of
+/// parameters, then by whether the values fit their types.
+pub fn encode_call(abi: &JsonAbi, name: &str, args: &[ParseResult]) -> Result<String, String> {
+    let functions = abi
+        .function(name)
+        .ok_or(format!("no function named {}", name))?;
+    let mut result = Err(format!("{} doesn't take {} values", name, args.len()));
+    for function in functions.iter().filter(|f| f.inputs.len() == args.len()) {
+        result = abi_encode_values(&function.signature(), args, true);
+        if result.is_ok() {
+            break;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi_of(input: &str) -> (Option<String>, JsonAbi) {
+        let parsed = parse_interface(input).unwrap().unwrap();
+        match parsed.get("interface") {
+            Some(name) => (
+                name.as_str().map(String::from),
+                serde_json::from_value(parsed["abi"].clone()).unwrap(),
+            ),
+            None => (None, serde_json::from_value(parsed).unwrap()),
+        }
+    }
+
+    #[test]
+    fn interface_with_pragma_imports_and_comments() {
+        let (name, abi) = abi_of(
+            "// SPDX-License-Identifier: MIT
+            pragma solidity ^0.8.20;
+            import {IERC165} from \"./IERC165.sol\";
+
+            /// @title ERC-20 /* not a block comment */
+            interface IERC20 is IERC165 {
+                /* moves `amount` tokens;
+                   returns whether it succeeded */
+                function transfer(address to, uint256 amount) external returns (bool); // ;
+                event Transfer(address indexed from, address indexed to, uint256 value);
+                error InsufficientBalance(uint256 available, uint256 required);
+            }",
+        );
+        assert_eq!(name.as_deref(), Some("IERC20"));
+        assert_eq!(
+            item_signature(&abi, "transfer").unwrap(),
+            "transfer(address,uint256)"
+        );
+        assert_eq!(
+            item_signature(&abi, "InsufficientBalance").unwrap(),
+            "InsufficientBalance(uint256,uint256)"
+        );
+        let transfer = &abi.event("Transfer").unwrap()[0];
+        assert_eq!(
+            transfer
+                .inputs
+                .iter()
+                .map(|p| p.indexed)
+                .collect::<Vec<_>>(),
+            [true, true, false]
+        );
+        assert_eq!(abi.functions().count(), 1);
+    }
+
+    #[test]
+    fn structs_enums_and_contract_types() {
+        let (_, abi) = abi_of(
+            "interface IPool {
+                enum Side { Buy, Sell }
+                type Price is uint128;
+                struct Order { address maker; Side side; Price price; IERC20 token; }
+                struct Fill { Order order; uint256[] amounts; }
+                function fill(Fill calldata fill, IPool.Order[] memory others) external returns (IERC20);
+                function owner() external view returns (address payable);
+            }",
+        );
+        assert_eq!(
+            item_signature(&abi, "fill").unwrap(),
+            "fill(((address,uint8,uint128,address),uint256[]),(address,uint8,uint128,address)[])"
+        );
+        let fill = &abi.function("fill").unwrap()[0];
+        assert_eq!(fill.outputs[0].ty, "address");
+        let names = |param: &Param| {
+            param
+                .components
+                .iter()
+                .map(|c| c.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&fill.inputs[0]), ["order", "amounts"]);
+        assert_eq!(
+            names(&fill.inputs[0].components[0]),
+            ["maker", "side", "price", "token"]
+        );
+        assert_eq!(names(&fill.inputs[1]), ["maker", "side", "price", "token"]);
+        assert_eq!(item_signature(&abi, "owner").unwrap(), "owner()");
+    }
+
+    #[test]
+    fn human_readable_items() {
+        let (name, abi) = abi_of(
+            "function balanceOf(address owner) view returns (uint256)
+            event Approval(address indexed owner, address indexed spender, uint256 value)",
+        );
+        assert!(name.is_none());
+        assert_eq!(
+            item_signature(&abi, "balanceOf").unwrap(),
+            "balanceOf(address)"
+        );
+        assert!(abi.event("Approval").is_some());
+    }
+
+    #[test]
+    fn not_an_interface() {
+        assert!(parse_interface("1 + 2").is_none());
+        assert!(parse_interface("interfaces").is_none());
+        assert!(parse_interface("contract Counter { uint256 number; }").is_none());
+    }
+
+    #[test]
+    fn unsupported_syntax() {
+        let mapping = "interface IRegistry {
+            function entries() external view returns (mapping(address => uint256));
+        }";
+        assert!(parse_interface(mapping).unwrap().is_err());
+        assert!(
+            parse_interface("interface IBroken { function f(uint256 external; }")
+                .unwrap()
+                .is_err()
+        );
+    }
+}
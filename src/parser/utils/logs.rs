@@ -0,0 +1,300 @@
+use super::signatures;
+use crate::parser::types::{
+    abi::{dyn_value_to_json, parse_json_abi},
+    result::ParseResult,
+};
+
+use alloy_core::primitives::{hex, B256};
+use alloy_dyn_abi::{EventExt, Word};
+use alloy_json_abi::{Event, JsonAbi};
+use serde_json::{json, Map, Value};
+
+/// Decodes a log from its topics and data, given an event signature
+/// (ie: `Transfer(address indexed from, address indexed to, uint256 value)`) or an ABI.
+/// Anonymous events are tried when no event matches topic0.
+pub fn decode_log(
+    abi: &ParseResult,
+    topics: &ParseResult,
+    data: &ParseResult,
+) -> Result<Value, String> {
+    let events = match abi {
+        ParseResult::String(sig) => {
+            vec![Event::parse(sig).map_err(|e| format!("invalid event signature: {}", e))?]
+        }
+        _ => parse_json_abi(abi)?.events().cloned().collect(),
+    };
+    let topics = match topics {
+        ParseResult::Array(topics) => topics.iter().map(to_topic).collect(),
+        ParseResult::Json(Value::Array(topics)) => topics
+            .iter()
+            .map(|t| match t {
+                Value::String(s) => to_topic(&ParseResult::String(s.clone())),
+                _ => Err("topics must be hex strings".to_string()),
+            })
+            .collect(),
+        topic => to_topic(topic).map(|t| vec![t]),
+    }?;
+    let data = match data {
+        ParseResult::String(s) => hex::decode(s).map_err(|e| format!("invalid data: {}", e))?,
+        ParseResult::Value(u) => u.to_be_bytes::<32>().to_vec(),
+        _ => return Err("data must be hex".to_string()),
+    };
+    match_event(&events, &topics, &data, true)
+        .unwrap_or(Err("no event matches the log".to_string()))
+}
+
+/// Decodes the logs of a pasted `eth_getTransactionReceipt` response (or a single log) with
+/// the ABIs and the signature database, keeping the unknown logs as they are.
+pub fn decode_receipt_logs(receipt: &Value, abis: &[JsonAbi]) -> Result<Value, String> {
+    let receipt = receipt.get("result").unwrap_or(receipt);
+    let logs = match (receipt.get("logs"), receipt.get("topics")) {
+        (Some(Value::Array(logs)), _) => logs.clone(),
+        (None, Some(_)) => vec![receipt.clone()],
+        _ => return Err("expected a transaction receipt or a log".to_string()),
+    };
+    logs.iter()
+        .map(|log| {
+            let topics = match log.get("topics") {
+                Some(Value::Array(topics)) => topics
+                    .iter()
+                    .map(|t| to_topic(&ParseResult::String(t.as_str().unwrap_or("").to_string())))
+                    .collect::<Result<Vec<B256>, String>>()?,
+                _ => return Err("log without topics".to_string()),
+            };
+            let data = hex::decode(log.get("data").and_then(Value::as_str).unwrap_or("0x"))
+                .map_err(|e| format!("invalid log data: {}", e))?;
+            let mut decoded = Map::new();
+            for field in ["address", "logIndex"] {
+                if let Some(value) = log.get(field) {
+                    decoded.insert(field.to_string(), value.clone());
+                }
+            }
+            // the notebook ABIs come first, then the signature database
+            let events: Vec<Event> = abis
+                .iter()
+                .flat_map(|abi| abi.events().cloned())
+                .chain(
+                    topics
+                        .first()
+                        .map_or(&[][..], |t| signatures::events(t.as_slice()))
+                        .iter()
+                        .cloned(),
+                )
+                .collect();
+            match match_event(&events, &topics, &data, false) {
+                Some(Ok(Value::Object(event))) => decoded.extend(event),
+                _ => {
+                    decoded.insert("topics".to_string(), log["topics"].clone());
+                    decoded.insert("data".to_string(), log["data"].clone());
+                }
+            }
+            Ok(Value::Object(decoded))
+        })
+        .collect::<Result<Vec<Value>, String>>()
+        .map(Value::Array)
+}
+
+/// Decodes the log with the events whose selector is topic0 (events sharing a topic0 may only
+/// differ by their indexed parameters), falling back to the anonymous events whose parameters
+/// fit the log.
+fn match_event(
+    events: &[Event],
+    topics: &[B256],
+    data: &[u8],
+    anonymous: bool,
+) -> Option<Result<Value, String>> {
+    let mut named = events
+        .iter()
+        .filter(|event| {
+            !event.anonymous
+                && topics
+                    .first()
+                    .is_some_and(|t| t.as_slice() == event.selector().as_slice())
+        })
+        .map(|event| decode_event(event, topics, data))
+        .collect::<Vec<_>>();
+    if !named.is_empty() {
+        let position = named.iter().position(Result::is_ok).unwrap_or(0);
+        return Some(named.swap_remove(position));
+    }
+    if !anonymous {
+        return None;
+    }
+    events
+        .iter()
+        .filter(|event| event.anonymous)
+        .map(|event| decode_event(event, topics, data))
+        .find(Result::is_ok)
+}
+
+fn decode_event(event: &Event, topics: &[B256], data: &[u8]) -> Result<Value, String> {
+    let decoded = event
+        .decode_log_parts(topics.iter().map(|t| Word::from(t.0)), data, true)
+        .map_err(|e| format!("failed to decode log: {}", e))?;
+    let (mut indexed, mut body) = (decoded.indexed.iter(), decoded.body.iter());
+    let mut args = Map::new();
+    for (i, param) in event.inputs.iter().enumerate() {
+        let value = if param.indexed {
+            indexed.next()
+        } else {
+            body.next()
+        };
+        let Some(value) = value else { break };
+        let name = if param.name.is_empty() {
+            format!("[{}]", i)
+        } else {
+            param.name.clone()
+        };
+        // indexed dynamic types (strings, bytes, arrays and tuples) only keep their hash
+        let key = if param.indexed {
+            format!("{} indexed {}", param.selector_type(), name)
+        } else {
+            format!("{} {}", param.selector_type(), name)
+        };
+        args.insert(key, dyn_value_to_json(value, &param.components));
+    }
+    let mut result = Map::new();
+    result.insert("event".to_string(), json!(event.signature()));
+    if event.anonymous {
+        result.insert("anonymous".to_string(), json!(true));
+    } else {
+        result.insert("topic0".to_string(), json!(topics[0].to_string()));
+    }
+    result.insert("args".to_string(), Value::Object(args));
+    Ok(Value::Object(result))
+}
+
+fn to_topic(topic: &ParseResult) -> Result<B256, String> {
+    match topic {
+        ParseResult::Value(u) => Ok(B256::from(*u)),
+        ParseResult::Address(a) => Ok(a.into_word()),
+        ParseResult::String(s) => {
+            let bytes = hex::decode(s).map_err(|e| format!("invalid topic: {}", e))?;
+            if bytes.len() != 32 {
+                return Err("topics must be 32 bytes".to_string());
+            }
+            Ok(B256::from_slice(&bytes))
+        }
+        _ => Err("topics must be 32-byte words".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_core::primitives::{keccak256, U256};
+
+    const TRANSFER: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+    const FROM: &str = "0x000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa96045";
+    const TO: &str = "0x000000000000000000000000000000000000000000000000000000000000dead";
+
+    fn string(s: &str) -> ParseResult {
+        ParseResult::String(s.to_string())
+    }
+
+    fn topics(topics: &[&str]) -> ParseResult {
+        ParseResult::Array(topics.iter().map(|t| string(t)).collect())
+    }
+
+    fn word(value: u64) -> String {
+        format!("0x{:064x}", value)
+    }
+
+    #[test]
+    fn indexed_and_data_parameters() {
+        let sig = string("Transfer(address indexed from, address indexed to, uint256 value)");
+        let decoded =
+            decode_log(&sig, &topics(&[TRANSFER, FROM, TO]), &string(&word(1000))).unwrap();
+        assert_eq!(
+            decoded,
+            json!({
+                "event": "Transfer(address,address,uint256)",
+                "topic0": TRANSFER,
+                "args": {
+                    "address indexed from": "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+                    "address indexed to": "0x000000000000000000000000000000000000dEaD",
+                    "uint256 value": "1000",
+                },
+            })
+        );
+        // a single word of data can be given as a number
+        let amount = ParseResult::Value(U256::from(1000));
+        assert_eq!(
+            decode_log(&sig, &topics(&[TRANSFER, FROM, TO]), &amount).unwrap(),
+            decoded
+        );
+    }
+
+    #[test]
+    fn indexed_dynamic_types_are_hashes() {
+        let sig = string("Registered(string indexed name, uint256 id)");
+        let topic0 = Event::parse("event Registered(string indexed name, uint256 id)")
+            .unwrap()
+            .selector()
+            .to_string();
+        let name = keccak256("alice").to_string();
+        let decoded = decode_log(&sig, &topics(&[&topic0, &name]), &string(&word(7))).unwrap();
+        assert_eq!(decoded["args"]["string indexed name"], name);
+        assert_eq!(decoded["args"]["uint256 id"], "7");
+    }
+
+    #[test]
+    fn anonymous_events() {
+        let abi = ParseResult::Json(json!([{
+            "type": "event",
+            "name": "Deposit",
+            "anonymous": true,
+            "inputs": [
+                { "name": "account", "type": "address", "indexed": true },
+                { "name": "amount", "type": "uint256", "indexed": false },
+            ],
+        }]));
+        let decoded = decode_log(&abi, &topics(&[FROM]), &string(&word(5))).unwrap();
+        assert_eq!(decoded["event"], "Deposit(address,uint256)");
+        assert_eq!(decoded["anonymous"], true);
+        assert!(decoded.get("topic0").is_none());
+        assert_eq!(decoded["args"]["uint256 amount"], "5");
+    }
+
+    #[test]
+    fn wrong_topic_count() {
+        let sig = string("Transfer(address indexed from, address indexed to, uint256 value)");
+        assert!(decode_log(&sig, &topics(&[TRANSFER, FROM]), &string(&word(1))).is_err());
+        assert!(decode_log(&sig, &topics(&[TRANSFER, FROM, TO, TO]), &string("0x")).is_err());
+        assert!(decode_log(&sig, &topics(&[TO, FROM, TO]), &string(&word(1))).is_err());
+    }
+
+    #[test]
+    fn receipt_with_known_and_unknown_logs() {
+        let unknown = keccak256("Unknown()").to_string();
+        let receipt = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "logs": [
+                    // ERC-20 transfer
+                    { "address": "0x1", "logIndex": "0x0", "topics": [TRANSFER, FROM, TO], "data": word(1000) },
+                    // ERC-721 transfer, sharing the ERC-20 topic0
+                    { "address": "0x2", "logIndex": "0x1", "topics": [TRANSFER, FROM, TO, word(42)], "data": "0x" },
+                    { "address": "0x3", "logIndex": "0x2", "topics": [unknown], "data": "0x01" },
+                ],
+            },
+        });
+        let decoded = decode_receipt_logs(&receipt, &[]).unwrap();
+        assert_eq!(decoded[0]["address"], "0x1");
+        assert_eq!(decoded[0]["args"]["uint256 value"], "1000");
+        assert_eq!(decoded[1]["logIndex"], "0x1");
+        assert_eq!(decoded[1]["args"]["uint256 indexed tokenId"], "42");
+        assert_eq!(
+            decoded[2],
+            json!({ "address": "0x3", "logIndex": "0x2", "topics": [unknown], "data": "0x01" })
+        );
+
+        // the notebook ABIs identify the unknown event
+        let abi = JsonAbi::parse(["event Unknown()"]).unwrap();
+        let decoded = decode_receipt_logs(&receipt, &[abi]).unwrap();
+        assert_eq!(decoded[2]["event"], "Unknown()");
+
+        assert!(decode_receipt_logs(&json!({ "status": "0x1" }), &[]).is_err());
+    }
+}
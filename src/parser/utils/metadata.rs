@@ -0,0 +1,341 @@
+use super::disassembler::metadata_range;
+use crate::parser::types::abi::named_values_to_json;
+
+use alloy_core::primitives::hex;
+use alloy_dyn_abi::JsonAbiExt;
+use alloy_json_abi::JsonAbi;
+use serde_json::{json, Map, Value};
+use std::ops::Range;
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Decodes the CBOR metadata solc appends to the runtime code: the compiler version, the IPFS
+/// hash of the metadata file (as a CIDv0, ie: `Qm..`) or its Swarm hash (`bzzr0`, `bzzr1`) and
+/// whether experimental features were enabled.
+///
+/// Release builds encode the version as 3 bytes, prereleases as the full version string.
+pub fn decode_metadata(code: &[u8]) -> Result<Value, String> {
+    let range = metadata_range(code).ok_or("no solc metadata at the end of the bytecode")?;
+    let cbor = &code[range.start..range.end - 2];
+    let mut metadata = Map::new();
+    for (key, value) in Cbor(cbor).map()? {
+        let value = match (key.as_str(), value) {
+            ("solc", CborValue::Bytes(v)) if v.len() == 3 => {
+                json!(format!("{}.{}.{}", v[0], v[1], v[2]))
+            }
+            ("ipfs", CborValue::Bytes(hash)) => json!(base58(hash)),
+            (_, CborValue::Bytes(bytes)) => json!(format!("0x{}", hex::encode(bytes))),
+            (_, CborValue::Text(text)) => json!(text),
+            (_, CborValue::Bool(b)) => json!(b),
+            (_, CborValue::Uint(u)) => json!(u),
+        };
+        metadata.insert(key, value);
+    }
+    metadata.insert("length".to_string(), json!(range.len()));
+    Ok(Value::Object(metadata))
+}
+
+/// Decodes the constructor arguments of a creation transaction, appended to the creation code
+/// of the contract. The creation code may differ from the transaction input in its metadata
+/// hashes only, ie: when compiled from another path, which is flagged as `metadataMismatch`.
+pub fn constructor_args(input: &[u8], creation: &[u8], abi: &JsonAbi) -> Result<Value, String> {
+    let masked = metadata_ranges(creation);
+    let mismatch = input
+        .iter()
+        .zip(creation)
+        .position(|(a, b)| a != b)
+        .or((input.len() < creation.len()).then_some(input.len()));
+    let mut metadata_mismatch = false;
+    if let Some(offset) = mismatch {
+        let only_metadata = input.len() >= creation.len()
+            && input
+                .iter()
+                .zip(creation)
+                .enumerate()
+                .all(|(i, (a, b))| a == b || masked.iter().any(|range| range.contains(&i)));
+        if !only_metadata {
+            return Err(format!(
+                "the input doesn't start with the creation code (differs at 0x{:04x})",
+                offset
+            ));
+        }
+        metadata_mismatch = true;
+    }
+
+    let data = &input[creation.len()..];
+    let inputs = abi
+        .constructor()
+        .map(|constructor| &constructor.inputs[..])
+        .unwrap_or_default();
+    let values = match abi.constructor() {
+        Some(constructor) => constructor
+            .abi_decode_input(data, false)
+            .map_err(|e| format!("failed to decode constructor arguments: {}", e))?,
+        None if data.is_empty() => vec![],
+        None => return Err("the ABI has no constructor, yet arguments were passed".to_string()),
+    };
+    let types: Vec<String> = inputs.iter().map(|p| p.selector_type().into()).collect();
+    let mut result = Map::new();
+    result.insert(
+        "constructor".to_string(),
+        json!(format!("constructor({})", types.join(","))),
+    );
+    result.insert("args".to_string(), named_values_to_json(&values, inputs));
+    result.insert(
+        "data".to_string(),
+        json!(format!("0x{}", hex::encode(data))),
+    );
+    if metadata_mismatch {
+        result.insert("metadataMismatch".to_string(), json!(true));
+    }
+    Ok(Value::Object(result))
+}
+
+/// Ranges of the metadata of the creation code, which holds the metadata of the runtime code
+/// (and of the contracts it deploys) in the middle of the bytecode.
+fn metadata_ranges(code: &[u8]) -> Vec<Range<usize>> {
+    (2..=code.len())
+        // the 2-byte length of the CBOR map, which solc keeps short
+        .filter(|end| code[end - 2] == 0 && (0x20..=0x80).contains(&code[end - 1]))
+        .filter_map(|end| {
+            let range = metadata_range(&code[..end])?;
+            Cbor(&code[range.start..end - 2]).map().ok().map(|_| range)
+        })
+        .collect()
+}
+
+enum CborValue<'a> {
+    Bytes(&'a [u8]),
+    Text(&'a str),
+    Bool(bool),
+    Uint(u64),
+}
+
+/// Reader of the CBOR subset solc emits: a map keyed by text strings, holding byte strings,
+/// text strings, booleans and small integers.
+struct Cbor<'a>(&'a [u8]);
+
+impl<'a> Cbor<'a> {
+    fn map(mut self) -> Result<Vec<(String, CborValue<'a>)>, String> {
+        let (major, len) = self.head()?;
+        if major != 5 {
+            return Err("expected a CBOR map".to_string());
+        }
+        let mut entries = Vec::new();
+        for _ in 0..len {
+            let key = match self.item()? {
+                CborValue::Text(key) => key.to_string(),
+                _ => return Err("expected CBOR text keys".to_string()),
+            };
+            entries.push((key, self.item()?));
+        }
+        if !self.0.is_empty() {
+            return Err("trailing bytes after the CBOR map".to_string());
+        }
+        Ok(entries)
+    }
+
+    fn item(&mut self) -> Result<CborValue<'a>, String> {
+        match self.0.first() {
+            Some(0xf4) => self.take(1).map(|_| CborValue::Bool(false)),
+            Some(0xf5) => self.take(1).map(|_| CborValue::Bool(true)),
+            _ => match self.head()? {
+                (0, value) => Ok(CborValue::Uint(value)),
+                (2, len) => self.take(len).map(CborValue::Bytes),
+                (3, len) => std::str::from_utf8(self.take(len)?)
+                    .map(CborValue::Text)
+                    .map_err(|e| format!("invalid CBOR text: {}", e)),
+                (major, _) => Err(format!("unsupported CBOR type {}", major)),
+            },
+        }
+    }
+
+    /// Major type and argument (the length, or the value of integers) of the next item.
+    fn head(&mut self) -> Result<(u8, u64), String> {
+        let byte = self.take(1)?[0];
+        let size = match byte & 0x1f {
+            info @ 0..=23 => return Ok((byte >> 5, info as u64)),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err("unsupported CBOR length".to_string()),
+        };
+        let value = self
+            .take(size)?
+            .iter()
+            .fold(0u64, |value, byte| value << 8 | *byte as u64);
+        Ok((byte >> 5, value))
+    }
+
+    fn take(&mut self, len: u64) -> Result<&'a [u8], String> {
+        let len = usize::try_from(len).map_err(|e| e.to_string())?;
+        if len > self.0.len() {
+            return Err("truncated CBOR".to_string());
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(head)
+    }
+}
+
+/// Base58btc encoding, which turns the sha2-256 multihash of an IPFS hash into its CIDv0.
+fn base58(bytes: &[u8]) -> String {
+    let mut digits: Vec<u8> = Vec::new();
+    for byte in bytes {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+    std::iter::repeat_n(BASE58_ALPHABET[0], zeros)
+        .chain(
+            digits
+                .iter()
+                .rev()
+                .map(|digit| BASE58_ALPHABET[*digit as usize]),
+        )
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The CBOR map of solc metadata with its 2-byte length, from `(key, encoded value)` pairs.
+    fn trailer(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut cbor = vec![0xa0 | entries.len() as u8];
+        for (key, value) in entries {
+            cbor.push(0x60 | key.len() as u8);
+            cbor.extend(key.as_bytes());
+            cbor.extend(value);
+        }
+        let len = cbor.len() as u16;
+        cbor.extend(len.to_be_bytes());
+        cbor
+    }
+
+    /// `ipfs` entry holding the sha2-256 multihash of the given digest.
+    fn ipfs(digest: [u8; 32]) -> Vec<u8> {
+        let mut value = vec![0x58, 0x22, 0x12, 0x20];
+        value.extend(digest);
+        value
+    }
+
+    fn code_with(metadata: &[u8]) -> Vec<u8> {
+        let mut code = hex::decode("6080604052348015600e575f80fd5b00fe").unwrap();
+        code.extend(metadata);
+        code
+    }
+
+    #[test]
+    fn base58_vectors() {
+        assert_eq!(base58(&[]), "");
+        assert_eq!(base58(&[0x61]), "2g");
+        assert_eq!(base58(&hex::decode("626262").unwrap()), "a3gV");
+        assert_eq!(base58(&hex::decode("516b6fcd0f").unwrap()), "ABnLTmg");
+        assert_eq!(base58(&[0; 10]), "1111111111");
+        assert_eq!(
+            base58(&hex::decode("00eb15231dfceb60925886b67d065299925915aeb172c06647").unwrap()),
+            "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L"
+        );
+    }
+
+    #[test]
+    fn release_metadata() {
+        // what solc 0.8.24 appends by default: the IPFS hash and the compiler version
+        let metadata = trailer(&[
+            ("ipfs", ipfs([0; 32])),
+            ("solc", vec![0x43, 0x00, 0x08, 0x18]),
+        ]);
+        assert_eq!(metadata.len(), 53);
+        let decoded = decode_metadata(&code_with(&metadata)).unwrap();
+        assert_eq!(
+            decoded,
+            json!({
+                "ipfs": "QmNLei78zWmzUdbeRB3CiUfAizWUrbeeZh5K1rhAQKCh51",
+                "solc": "0.8.24",
+                "length": 53,
+            })
+        );
+    }
+
+    #[test]
+    fn prerelease_and_experimental_metadata() {
+        let version = "0.8.26-nightly.2024.5.1+commit.5f1ea9c6";
+        let mut solc = vec![0x78, version.len() as u8];
+        solc.extend(version.as_bytes());
+        let metadata = trailer(&[
+            ("bzzr1", [vec![0x58, 0x20], vec![0xaa; 32]].concat()),
+            ("experimental", vec![0xf5]),
+            ("solc", solc),
+        ]);
+        let decoded = decode_metadata(&code_with(&metadata)).unwrap();
+        assert_eq!(decoded["solc"], version);
+        assert_eq!(decoded["experimental"], true);
+        assert_eq!(decoded["bzzr1"], format!("0x{}", "aa".repeat(32)));
+
+        assert!(decode_metadata(&hex::decode("6080604052").unwrap()).is_err());
+    }
+
+    #[test]
+    fn constructor_arguments() {
+        let abi = JsonAbi::parse(["constructor(address owner, uint256 supply)"]).unwrap();
+        let creation = code_with(&trailer(&[
+            ("ipfs", ipfs([1; 32])),
+            ("solc", vec![0x43, 0x00, 0x08, 0x18]),
+        ]));
+        let args = format!("{:064x}{:064x}", 0xdead, 1000);
+        let input = [creation.clone(), hex::decode(&args).unwrap()].concat();
+
+        let decoded = constructor_args(&input, &creation, &abi).unwrap();
+        assert_eq!(decoded["constructor"], "constructor(address,uint256)");
+        assert_eq!(
+            decoded["args"],
+            json!({
+                "address owner": "0x000000000000000000000000000000000000dEaD",
+                "uint256 supply": "1000",
+            })
+        );
+        assert_eq!(decoded["data"], format!("0x{}", args));
+        assert!(decoded.get("metadataMismatch").is_none());
+
+        // compiled from another path: only the metadata hash differs
+        let other = code_with(&trailer(&[
+            ("ipfs", ipfs([2; 32])),
+            ("solc", vec![0x43, 0x00, 0x08, 0x18]),
+        ]));
+        let decoded = constructor_args(&input, &other, &abi).unwrap();
+        assert_eq!(decoded["metadataMismatch"], true);
+        assert_eq!(decoded["data"], format!("0x{}", args));
+    }
+
+    #[test]
+    fn mismatched_constructor_input() {
+        let abi = JsonAbi::parse(["constructor(uint256 supply)"]).unwrap();
+        let creation = code_with(&[]);
+        let mut input = creation.clone();
+        input[0] = 0x61;
+        input.extend([0; 32]);
+        assert_eq!(
+            constructor_args(&input, &creation, &abi).unwrap_err(),
+            "the input doesn't start with the creation code (differs at 0x0000)"
+        );
+        // truncated arguments
+        assert!(
+            constructor_args(&[creation.clone(), vec![0; 31]].concat(), &creation, &abi).is_err()
+        );
+        // arguments to a contract without a constructor
+        let no_constructor = JsonAbi::parse(["function f()"]).unwrap();
+        assert!(constructor_args(&input[..], &input[..input.len() - 32], &no_constructor).is_err());
+        assert!(constructor_args(&creation[..4], &creation, &abi).is_err());
+    }
+}
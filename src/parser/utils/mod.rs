@@ -1,4 +1,23 @@
+pub mod artifact;
+pub mod calldata;
+pub mod disassembler;
+pub mod eip712;
+pub mod ens;
+pub mod executor;
+pub mod interface;
+pub mod logs;
+pub mod metadata;
+pub mod multicall;
+pub mod opcodes;
+pub mod precompiles;
+pub mod revert;
+pub mod rlp;
+pub mod selectors;
+pub mod signature;
+pub mod signatures;
+pub mod tx;
 pub mod uniswap_v3;
+pub mod user_op;
 use super::types::result::ParseResult;
 
 use alloy_core::primitives::{Address, B256, U256};
@@ -90,10 +109,21 @@ pub fn split_top_level(input: &str) -> Vec<String> {
     let mut current = String::new();
     let mut bracket_depth = 0;
     let mut parenthesis_depth = 0;
+    let mut quote = None;
     let mut chars = input.chars().peekable();
 
     while let Some(c) = chars.next() {
         match c {
+            // commas and brackets inside quoted strings are part of the value
+            '"' | '\'' if quote.is_none() => {
+                quote = Some(c);
+                current.push(c);
+            }
+            c if quote == Some(c) => {
+                quote = None;
+                current.push(c);
+            }
+            _ if quote.is_some() => current.push(c),
             ',' if bracket_depth == 0 && parenthesis_depth == 0 => {
                 // If we're not inside any brackets or parentheses, split here
                 result.push(current.trim().to_string());
@@ -0,0 +1,203 @@
+use super::revert;
+use crate::parser::types::{
+    abi::{abi_decode_calldata, abi_encode_values, named_values_to_json},
+    result::ParseResult,
+};
+
+use alloy_core::primitives::hex;
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_json_abi::Function;
+use serde_json::{json, Map, Value};
+
+/// `Multicall3.aggregate3((address target, bool allowFailure, bytes callData)[])`.
+const AGGREGATE3: &str = "aggregate3((address,bool,bytes)[])";
+/// `Multicall3.tryAggregate(bool requireSuccess, (address target, bytes callData)[])`.
+const TRY_AGGREGATE: &str = "tryAggregate(bool,(address,bytes)[])";
+
+/// Encodes a batch of `(target, allowFailure, calldata)` calls for `aggregate3`.
+pub fn encode_aggregate3(calls: &ParseResult) -> Result<String, String> {
+    abi_encode_values(AGGREGATE3, std::slice::from_ref(calls), true)
+}
+
+/// Encodes a batch of `(target, calldata)` calls for `tryAggregate`.
+pub fn encode_try_aggregate(
+    require_success: &ParseResult,
+    calls: &ParseResult,
+) -> Result<String, String> {
+    abi_encode_values(
+        TRY_AGGREGATE,
+        &[require_success.clone(), calls.clone()],
+        true,
+    )
+}
+
+/// Decodes the `(bool success, bytes returnData)[]` returned by `aggregate3` and `tryAggregate`,
+/// then the return data of each call with its signature (ie: `balanceOf(address)(uint256)`).
+/// Failed calls have their revert data decoded instead, and calls without a signature (or whose
+/// return data doesn't match it) keep their raw return data.
+pub fn decode_results(returndata: &[u8], sigs: &[String]) -> Result<Value, String> {
+    let ty = DynSolType::Array(Box::new(DynSolType::Tuple(vec![
+        DynSolType::Bool,
+        DynSolType::Bytes,
+    ])));
+    let results = match ty.abi_decode(returndata) {
+        Ok(DynSolValue::Array(results)) => results,
+        _ => return Err("expected the (bool,bytes)[] results of a multicall".to_string()),
+    };
+    results
+        .iter()
+        .enumerate()
+        .map(|(i, result)| {
+            let (success, data) = match result.as_tuple() {
+                Some([DynSolValue::Bool(success), DynSolValue::Bytes(data)]) => (*success, data),
+                _ => return Err("invalid multicall result".to_string()),
+            };
+            let mut decoded = Map::new();
+            let sig = sigs.get(i).filter(|sig| !sig.is_empty());
+            if let Some(sig) = sig {
+                decoded.insert("function".to_string(), json!(sig));
+            }
+            decoded.insert("success".to_string(), json!(success));
+            match (success, sig) {
+                (false, _) => {
+                    let error = revert::decode_revert(data, None)
+                        .unwrap_or(json!(format!("0x{}", hex::encode(data))));
+                    decoded.insert("revert".to_string(), error);
+                }
+                (true, Some(sig)) => match decode_return_data(sig, data) {
+                    Ok(result) => {
+                        decoded.insert("result".to_string(), result);
+                    }
+                    Err(e) => {
+                        decoded.insert("error".to_string(), json!(e));
+                        decoded.insert(
+                            "returnData".to_string(),
+                            json!(format!("0x{}", hex::encode(data))),
+                        );
+                    }
+                },
+                (true, None) => {
+                    decoded.insert(
+                        "returnData".to_string(),
+                        json!(format!("0x{}", hex::encode(data))),
+                    );
+                }
+            }
+            Ok(Value::Object(decoded))
+        })
+        .collect::<Result<Vec<Value>, String>>()
+        .map(Value::Array)
+}
+
+/// Decodes return data with the outputs of a signature, written as
+/// `balanceOf(address)(uint256)` or `balanceOf(address) returns (uint256)`.
+fn decode_return_data(sig: &str, data: &[u8]) -> Result<Value, String> {
+    let sig = match sig.find(")(") {
+        Some(i) if !sig.contains("returns") => format!("{} returns {}", &sig[..=i], &sig[i + 1..]),
+        _ => sig.to_string(),
+    };
+    let func = Function::parse(&sig).map_err(|e| format!("invalid signature {}: {}", sig, e))?;
+    let values = abi_decode_calldata(&sig, &hex::encode(data), false, false)
+        .map_err(|e| format!("{}: {}", func.signature(), e))?;
+    Ok(named_values_to_json(&values, &func.outputs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_core::primitives::U256;
+
+    const TARGET: &str = "0x00000000000000000000000000000000000000AA";
+    /// `balanceOf(0xbb)`
+    const BALANCE_OF: &str =
+        "0x70a0823100000000000000000000000000000000000000000000000000000000000000bb";
+
+    fn string(s: &str) -> ParseResult {
+        ParseResult::String(s.to_string())
+    }
+
+    fn result(success: bool, data: Vec<u8>) -> DynSolValue {
+        DynSolValue::Tuple(vec![DynSolValue::Bool(success), DynSolValue::Bytes(data)])
+    }
+
+    #[test]
+    fn encode_calls() {
+        let calls = ParseResult::Array(vec![
+            ParseResult::Array(vec![string(TARGET), string("false"), string(BALANCE_OF)]),
+            ParseResult::Array(vec![string(TARGET), string("true"), string("0x18160ddd")]),
+        ]);
+        let calldata = encode_aggregate3(&calls).unwrap();
+        assert!(calldata.starts_with("0x82ad56cb"));
+        let decoded = abi_decode_calldata(AGGREGATE3, &calldata, true, true).unwrap();
+        let DynSolValue::Array(decoded) = &decoded[0] else {
+            panic!("expected the calls");
+        };
+        assert_eq!(decoded.len(), 2);
+        let call = decoded[0].as_tuple().unwrap();
+        assert_eq!(call[0], DynSolType::Address.coerce_str(TARGET).unwrap());
+        assert_eq!(call[1], DynSolValue::Bool(false));
+        assert_eq!(
+            call[2],
+            DynSolValue::Bytes(hex::decode(BALANCE_OF).unwrap())
+        );
+        assert_eq!(decoded[1].as_tuple().unwrap()[1], DynSolValue::Bool(true));
+
+        let calls = ParseResult::Array(vec![ParseResult::Array(vec![
+            string(TARGET),
+            string(BALANCE_OF),
+        ])]);
+        let calldata = encode_try_aggregate(&ParseResult::Value(U256::ZERO), &calls).unwrap();
+        assert!(calldata.starts_with("0xbce38bd7"));
+        let decoded = abi_decode_calldata(TRY_AGGREGATE, &calldata, true, true).unwrap();
+        assert_eq!(decoded[0], DynSolValue::Bool(false));
+
+        // aggregate3 calls take an allowFailure flag
+        let calls = ParseResult::Array(vec![ParseResult::Array(vec![
+            string(TARGET),
+            string(BALANCE_OF),
+        ])]);
+        assert!(encode_aggregate3(&calls).is_err());
+    }
+
+    #[test]
+    fn decode_successful_and_reverted_calls() {
+        let mut revert = hex::decode("08c379a0").unwrap();
+        revert.extend(DynSolValue::String("nope".to_string()).abi_encode_params());
+        let returndata = DynSolValue::Array(vec![
+            result(true, U256::from(1000).to_be_bytes::<32>().to_vec()),
+            result(false, revert),
+            result(true, vec![0xab]),
+            result(true, vec![]),
+        ])
+        .abi_encode();
+        let sigs = [
+            "balanceOf(address)(uint256)",
+            "transfer(address,uint256)(bool)",
+            "totalSupply() returns (uint256 supply)",
+        ]
+        .map(String::from);
+        let decoded = decode_results(&returndata, &sigs).unwrap();
+        assert_eq!(
+            decoded[0],
+            json!({
+                "function": "balanceOf(address)(uint256)",
+                "success": true,
+                "result": { "uint256 [0]": "1000" },
+            })
+        );
+        assert_eq!(decoded[1]["success"], false);
+        assert_eq!(decoded[1]["revert"]["reason"], "nope");
+        assert!(decoded[1].get("result").is_none());
+        // the return data doesn't match the signature
+        assert_eq!(decoded[2]["success"], true);
+        assert_eq!(decoded[2]["returnData"], "0xab");
+        assert!(decoded[2]["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("totalSupply()"));
+        // calls without a signature keep their return data
+        assert_eq!(decoded[3], json!({ "success": true, "returnData": "0x" }));
+
+        assert!(decode_results(&[0xff], &[]).is_err());
+    }
+}
@@ -0,0 +1,498 @@
+/// An EVM instruction, with the number of stack items it takes and pushes, and its base gas
+/// cost as of Cancun (the warm cost for the instructions accessing accounts and storage).
+#[derive(Debug, PartialEq)]
+pub struct Opcode {
+    pub code: u8,
+    pub name: &'static str,
+    pub inputs: u8,
+    pub outputs: u8,
+    pub gas: u16,
+}
+
+const fn op(code: u8, name: &'static str, inputs: u8, outputs: u8, gas: u16) -> Opcode {
+    Opcode {
+        code,
+        name,
+        inputs,
+        outputs,
+        gas,
+    }
+}
+
+/// The mainnet hardforks that changed the instruction set or the gas schedule.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Fork {
+    Frontier,
+    Homestead,
+    TangerineWhistle,
+    SpuriousDragon,
+    Byzantium,
+    Constantinople,
+    Istanbul,
+    Berlin,
+    London,
+    Paris,
+    Shanghai,
+    Cancun,
+}
+
+impl Fork {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Fork::Frontier => "Frontier",
+            Fork::Homestead => "Homestead",
+            Fork::TangerineWhistle => "Tangerine Whistle",
+            Fork::SpuriousDragon => "Spurious Dragon",
+            Fork::Byzantium => "Byzantium",
+            Fork::Constantinople => "Constantinople",
+            Fork::Istanbul => "Istanbul",
+            Fork::Berlin => "Berlin",
+            Fork::London => "London",
+            Fork::Paris => "Paris",
+            Fork::Shanghai => "Shanghai",
+            Fork::Cancun => "Cancun",
+        }
+    }
+}
+
+/// A hardfork introducing an instruction, or changing its gas cost.
+#[derive(Debug, PartialEq)]
+pub struct Upgrade {
+    pub code: u8,
+    pub fork: Fork,
+    pub eip: Option<u16>,
+    pub introduced: bool,
+    pub gas: &'static str,
+}
+
+const fn added(code: u8, fork: Fork, eip: u16, gas: &'static str) -> Upgrade {
+    Upgrade {
+        code,
+        fork,
+        eip: Some(eip),
+        introduced: true,
+        gas,
+    }
+}
+
+const fn repriced(code: u8, fork: Fork, eip: Option<u16>, gas: &'static str) -> Upgrade {
+    Upgrade {
+        code,
+        fork,
+        eip,
+        introduced: false,
+        gas,
+    }
+}
+
+/// The instructions of the EVM (as of Cancun), ordered by byte.
+pub const OPCODES: [Opcode; 149] = [
+    op(0x00, "STOP", 0, 0, 0),
+    op(0x01, "ADD", 2, 1, 3),
+    op(0x02, "MUL", 2, 1, 5),
+    op(0x03, "SUB", 2, 1, 3),
+    op(0x04, "DIV", 2, 1, 5),
+    op(0x05, "SDIV", 2, 1, 5),
+    op(0x06, "MOD", 2, 1, 5),
+    op(0x07, "SMOD", 2, 1, 5),
+    op(0x08, "ADDMOD", 3, 1, 8),
+    op(0x09, "MULMOD", 3, 1, 8),
+    op(0x0a, "EXP", 2, 1, 10),
+    op(0x0b, "SIGNEXTEND", 2, 1, 5),
+    op(0x10, "LT", 2, 1, 3),
+    op(0x11, "GT", 2, 1, 3),
+    op(0x12, "SLT", 2, 1, 3),
+    op(0x13, "SGT", 2, 1, 3),
+    op(0x14, "EQ", 2, 1, 3),
+    op(0x15, "ISZERO", 1, 1, 3),
+    op(0x16, "AND", 2, 1, 3),
+    op(0x17, "OR", 2, 1, 3),
+    op(0x18, "XOR", 2, 1, 3),
+    op(0x19, "NOT", 1, 1, 3),
+    op(0x1a, "BYTE", 2, 1, 3),
+    op(0x1b, "SHL", 2, 1, 3),
+    op(0x1c, "SHR", 2, 1, 3),
+    op(0x1d, "SAR", 2, 1, 3),
+    op(0x20, "KECCAK256", 2, 1, 30),
+    op(0x30, "ADDRESS", 0, 1, 2),
+    op(0x31, "BALANCE", 1, 1, 100),
+    op(0x32, "ORIGIN", 0, 1, 2),
+    op(0x33, "CALLER", 0, 1, 2),
+    op(0x34, "CALLVALUE", 0, 1, 2),
+    op(0x35, "CALLDATALOAD", 1, 1, 3),
+    op(0x36, "CALLDATASIZE", 0, 1, 2),
+    op(0x37, "CALLDATACOPY", 3, 0, 3),
+    op(0x38, "CODESIZE", 0, 1, 2),
+    op(0x39, "CODECOPY", 3, 0, 3),
+    op(0x3a, "GASPRICE", 0, 1, 2),
+    op(0x3b, "EXTCODESIZE", 1, 1, 100),
+    op(0x3c, "EXTCODECOPY", 4, 0, 100),
+    op(0x3d, "RETURNDATASIZE", 0, 1, 2),
+    op(0x3e, "RETURNDATACOPY", 3, 0, 3),
+    op(0x3f, "EXTCODEHASH", 1, 1, 100),
+    op(0x40, "BLOCKHASH", 1, 1, 20),
+    op(0x41, "COINBASE", 0, 1, 2),
+    op(0x42, "TIMESTAMP", 0, 1, 2),
+    op(0x43, "NUMBER", 0, 1, 2),
+    op(0x44, "PREVRANDAO", 0, 1, 2),
+    op(0x45, "GASLIMIT", 0, 1, 2),
+    op(0x46, "CHAINID", 0, 1, 2),
+    op(0x47, "SELFBALANCE", 0, 1, 5),
+    op(0x48, "BASEFEE", 0, 1, 2),
+    op(0x49, "BLOBHASH", 1, 1, 3),
+    op(0x4a, "BLOBBASEFEE", 0, 1, 2),
+    op(0x50, "POP", 1, 0, 2),
+    op(0x51, "MLOAD", 1, 1, 3),
+    op(0x52, "MSTORE", 2, 0, 3),
+    op(0x53, "MSTORE8", 2, 0, 3),
+    op(0x54, "SLOAD", 1, 1, 100),
+    op(0x55, "SSTORE", 2, 0, 100),
+    op(0x56, "JUMP", 1, 0, 8),
+    op(0x57, "JUMPI", 2, 0, 10),
+    op(0x58, "PC", 0, 1, 2),
+    op(0x59, "MSIZE", 0, 1, 2),
+    op(0x5a, "GAS", 0, 1, 2),
+    op(0x5b, "JUMPDEST", 0, 0, 1),
+    op(0x5c, "TLOAD", 1, 1, 100),
+    op(0x5d, "TSTORE", 2, 0, 100),
+    op(0x5e, "MCOPY", 3, 0, 3),
+    op(0x5f, "PUSH0", 0, 1, 2),
+    op(0x60, "PUSH1", 0, 1, 3),
+    op(0x61, "PUSH2", 0, 1, 3),
+    op(0x62, "PUSH3", 0, 1, 3),
+    op(0x63, "PUSH4", 0, 1, 3),
+    op(0x64, "PUSH5", 0, 1, 3),
+    op(0x65, "PUSH6", 0, 1, 3),
+    op(0x66, "PUSH7", 0, 1, 3),
+    op(0x67, "PUSH8", 0, 1, 3),
+    op(0x68, "PUSH9", 0, 1, 3),
+    op(0x69, "PUSH10", 0, 1, 3),
+    op(0x6a, "PUSH11", 0, 1, 3),
+    op(0x6b, "PUSH12", 0, 1, 3),
+    op(0x6c, "PUSH13", 0, 1, 3),
+    op(0x6d, "PUSH14", 0, 1, 3),
+    op(0x6e, "PUSH15", 0, 1, 3),
+    op(0x6f, "PUSH16", 0, 1, 3),
+    op(0x70, "PUSH17", 0, 1, 3),
+    op(0x71, "PUSH18", 0, 1, 3),
+    op(0x72, "PUSH19", 0, 1, 3),
+    op(0x73, "PUSH20", 0, 1, 3),
+    op(0x74, "PUSH21", 0, 1, 3),
+    op(0x75, "PUSH22", 0, 1, 3),
+    op(0x76, "PUSH23", 0, 1, 3),
+    op(0x77, "PUSH24", 0, 1, 3),
+    op(0x78, "PUSH25", 0, 1, 3),
+    op(0x79, "PUSH26", 0, 1, 3),
+    op(0x7a, "PUSH27", 0, 1, 3),
+    op(0x7b, "PUSH28", 0, 1, 3),
+    op(0x7c, "PUSH29", 0, 1, 3),
+    op(0x7d, "PUSH30", 0, 1, 3),
+    op(0x7e, "PUSH31", 0, 1, 3),
+    op(0x7f, "PUSH32", 0, 1, 3),
+    op(0x80, "DUP1", 1, 2, 3),
+    op(0x81, "DUP2", 2, 3, 3),
+    op(0x82, "DUP3", 3, 4, 3),
+    op(0x83, "DUP4", 4, 5, 3),
+    op(0x84, "DUP5", 5, 6, 3),
+    op(0x85, "DUP6", 6, 7, 3),
+    op(0x86, "DUP7", 7, 8, 3),
+    op(0x87, "DUP8", 8, 9, 3),
+    op(0x88, "DUP9", 9, 10, 3),
+    op(0x89, "DUP10", 10, 11, 3),
+    op(0x8a, "DUP11", 11, 12, 3),
+    op(0x8b, "DUP12", 12, 13, 3),
+    op(0x8c, "DUP13", 13, 14, 3),
+    op(0x8d, "DUP14", 14, 15, 3),
+    op(0x8e, "DUP15", 15, 16, 3),
+    op(0x8f, "DUP16", 16, 17, 3),
+    op(0x90, "SWAP1", 2, 2, 3),
+    op(0x91, "SWAP2", 3, 3, 3),
+    op(0x92, "SWAP3", 4, 4, 3),
+    op(0x93, "SWAP4", 5, 5, 3),
+    op(0x94, "SWAP5", 6, 6, 3),
+    op(0x95, "SWAP6", 7, 7, 3),
+    op(0x96, "SWAP7", 8, 8, 3),
+    op(0x97, "SWAP8", 9, 9, 3),
+    op(0x98, "SWAP9", 10, 10, 3),
+    op(0x99, "SWAP10", 11, 11, 3),
+    op(0x9a, "SWAP11", 12, 12, 3),
+    op(0x9b, "SWAP12", 13, 13, 3),
+    op(0x9c, "SWAP13", 14, 14, 3),
+    op(0x9d, "SWAP14", 15, 15, 3),
+    op(0x9e, "SWAP15", 16, 16, 3),
+    op(0x9f, "SWAP16", 17, 17, 3),
+    op(0xa0, "LOG0", 2, 0, 375),
+    op(0xa1, "LOG1", 3, 0, 750),
+    op(0xa2, "LOG2", 4, 0, 1125),
+    op(0xa3, "LOG3", 5, 0, 1500),
+    op(0xa4, "LOG4", 6, 0, 1875),
+    op(0xf0, "CREATE", 3, 1, 32000),
+    op(0xf1, "CALL", 7, 1, 100),
+    op(0xf2, "CALLCODE", 7, 1, 100),
+    op(0xf3, "RETURN", 2, 0, 0),
+    op(0xf4, "DELEGATECALL", 6, 1, 100),
+    op(0xf5, "CREATE2", 4, 1, 32000),
+    op(0xfa, "STATICCALL", 6, 1, 100),
+    op(0xfd, "REVERT", 2, 0, 0),
+    op(0xfe, "INVALID", 0, 0, 0),
+    op(0xff, "SELFDESTRUCT", 1, 0, 5000),
+];
+
+/// Looks up an instruction by its byte, `None` for unassigned bytes.
+pub fn opcode(code: u8) -> Option<&'static Opcode> {
+    OPCODES
+        .binary_search_by_key(&code, |op| op.code)
+        .ok()
+        .map(|i| &OPCODES[i])
+}
+
+/// Number of bytes following `PUSH1`..`PUSH32`.
+pub fn immediate_size(code: u8) -> usize {
+    match code {
+        0x60..=0x7f => (code - 0x5f) as usize,
+        _ => 0,
+    }
+}
+
+/// Whether the instruction ends a basic block: jumps and the halting instructions.
+pub fn is_terminator(code: u8) -> bool {
+    matches!(code, 0x00 | 0x56 | 0x57 | 0xf3 | 0xfd | 0xfe | 0xff)
+}
+
+/// The instructions added after Frontier and the gas repricings, ordered by byte then fork.
+/// Repriced instructions start with their Frontier cost.
+pub const UPGRADES: [Upgrade; 56] = [
+    repriced(
+        0x0a,
+        Fork::Frontier,
+        None,
+        "10, +10 per byte of the exponent",
+    ),
+    repriced(
+        0x0a,
+        Fork::SpuriousDragon,
+        Some(160),
+        "10, +50 per byte of the exponent",
+    ),
+    added(0x1b, Fork::Constantinople, 145, "3"),
+    added(0x1c, Fork::Constantinople, 145, "3"),
+    added(0x1d, Fork::Constantinople, 145, "3"),
+    repriced(0x31, Fork::Frontier, None, "20"),
+    repriced(0x31, Fork::TangerineWhistle, Some(150), "400"),
+    repriced(0x31, Fork::Istanbul, Some(1884), "700"),
+    repriced(0x31, Fork::Berlin, Some(2929), "2600 cold, 100 warm"),
+    repriced(0x3b, Fork::Frontier, None, "20"),
+    repriced(0x3b, Fork::TangerineWhistle, Some(150), "700"),
+    repriced(0x3b, Fork::Berlin, Some(2929), "2600 cold, 100 warm"),
+    repriced(0x3c, Fork::Frontier, None, "20"),
+    repriced(0x3c, Fork::TangerineWhistle, Some(150), "700"),
+    repriced(0x3c, Fork::Berlin, Some(2929), "2600 cold, 100 warm"),
+    added(0x3d, Fork::Byzantium, 211, "2"),
+    added(0x3e, Fork::Byzantium, 211, "3"),
+    added(0x3f, Fork::Constantinople, 1052, "400"),
+    repriced(0x3f, Fork::Istanbul, Some(1884), "700"),
+    repriced(0x3f, Fork::Berlin, Some(2929), "2600 cold, 100 warm"),
+    repriced(
+        0x44,
+        Fork::Paris,
+        Some(4399),
+        "2, DIFFICULTY replaced by PREVRANDAO",
+    ),
+    added(0x46, Fork::Istanbul, 1344, "2"),
+    added(0x47, Fork::Istanbul, 1884, "5"),
+    added(0x48, Fork::London, 3198, "2"),
+    added(0x49, Fork::Cancun, 4844, "3"),
+    added(0x4a, Fork::Cancun, 7516, "2"),
+    repriced(0x54, Fork::Frontier, None, "50"),
+    repriced(0x54, Fork::TangerineWhistle, Some(150), "200"),
+    repriced(0x54, Fork::Istanbul, Some(1884), "800"),
+    repriced(0x54, Fork::Berlin, Some(2929), "2100 cold, 100 warm"),
+    repriced(
+        0x55,
+        Fork::Frontier,
+        None,
+        "20000 to set a slot, 5000 otherwise",
+    ),
+    repriced(
+        0x55,
+        Fork::Istanbul,
+        Some(2200),
+        "net metering, 800 for no-ops",
+    ),
+    repriced(
+        0x55,
+        Fork::Berlin,
+        Some(2929),
+        "+2100 for cold slots, 100 for no-ops",
+    ),
+    repriced(
+        0x55,
+        Fork::London,
+        Some(3529),
+        "4800 refund for clearing a slot",
+    ),
+    added(0x5c, Fork::Cancun, 1153, "100"),
+    added(0x5d, Fork::Cancun, 1153, "100"),
+    added(0x5e, Fork::Cancun, 5656, "3"),
+    added(0x5f, Fork::Shanghai, 3855, "2"),
+    repriced(0xf0, Fork::Shanghai, Some(3860), "+2 per word of init code"),
+    repriced(0xf1, Fork::Frontier, None, "40"),
+    repriced(0xf1, Fork::TangerineWhistle, Some(150), "700"),
+    repriced(0xf1, Fork::Berlin, Some(2929), "2600 cold, 100 warm"),
+    repriced(0xf2, Fork::Frontier, None, "40"),
+    repriced(0xf2, Fork::TangerineWhistle, Some(150), "700"),
+    repriced(0xf2, Fork::Berlin, Some(2929), "2600 cold, 100 warm"),
+    added(0xf4, Fork::Homestead, 7, "40"),
+    repriced(0xf4, Fork::TangerineWhistle, Some(150), "700"),
+    repriced(0xf4, Fork::Berlin, Some(2929), "2600 cold, 100 warm"),
+    added(
+        0xf5,
+        Fork::Constantinople,
+        1014,
+        "32000, +6 per word of init code",
+    ),
+    added(0xfa, Fork::Byzantium, 214, "700"),
+    repriced(0xfa, Fork::Berlin, Some(2929), "2600 cold, 100 warm"),
+    added(0xfd, Fork::Byzantium, 140, "0"),
+    repriced(0xff, Fork::Frontier, None, "0"),
+    repriced(0xff, Fork::TangerineWhistle, Some(150), "5000"),
+    repriced(0xff, Fork::London, Some(3529), "no refund"),
+    repriced(
+        0xff,
+        Fork::Cancun,
+        Some(6780),
+        "only deletes contracts created in the same transaction",
+    ),
+];
+
+/// Upgrades of an instruction, oldest first.
+pub fn upgrades(code: u8) -> impl Iterator<Item = &'static Upgrade> {
+    UPGRADES.iter().filter(move |upgrade| upgrade.code == code)
+}
+
+/// The part of the gas cost of an instruction depending on its operands or the state.
+pub fn dynamic_gas(code: u8) -> Option<&'static str> {
+    Some(match code {
+        0x0a => "+50 per byte of the exponent",
+        0x20 => "+6 per word hashed, +memory expansion",
+        0x31 | 0x3b | 0x3f => "+2500 if the address is cold",
+        0x37 | 0x39 | 0x3e | 0x5e => "+3 per word copied, +memory expansion",
+        0x3c => "+3 per word copied, +memory expansion, +2500 if the address is cold",
+        0x51..=0x53 | 0xf3 | 0xfd => "+memory expansion",
+        0x54 => "+2000 if the slot is cold",
+        0x55 => "+19900 to set a zero slot, +2800 to update it, +2100 if the slot is cold",
+        0xa0..=0xa4 => "+8 per byte of data, +memory expansion",
+        0xf0 => "+2 per word of init code, +memory expansion, +200 per byte of deployed code",
+        0xf5 => "+8 per word of init code, +memory expansion, +200 per byte of deployed code",
+        0xf1 => "+2500 if the address is cold, +9000 to send value, +25000 to create an account, +memory expansion",
+        0xf2 => "+2500 if the address is cold, +9000 to send value, +memory expansion",
+        0xf4 | 0xfa => "+2500 if the address is cold, +memory expansion",
+        0xff => "+2600 if the beneficiary is cold, +25000 to send value to a new account",
+        _ => return None,
+    })
+}
+
+/// A precompiled contract, at a fixed address.
+#[derive(Debug, PartialEq)]
+pub struct Precompile {
+    pub address: u8,
+    pub name: &'static str,
+    pub fork: Fork,
+    pub eip: Option<u16>,
+    /// Gas cost as of Cancun.
+    pub gas: &'static str,
+    /// Changes of the gas cost, with the fork and EIP making them.
+    pub repricings: &'static [(Fork, u16, &'static str)],
+}
+
+/// The precompiled contracts of mainnet (as of Cancun), ordered by address.
+pub const PRECOMPILES: [Precompile; 10] = [
+    Precompile {
+        address: 0x01,
+        name: "ecrecover",
+        fork: Fork::Frontier,
+        eip: None,
+        gas: "3000",
+        repricings: &[],
+    },
+    Precompile {
+        address: 0x02,
+        name: "sha256",
+        fork: Fork::Frontier,
+        eip: None,
+        gas: "60, +12 per word",
+        repricings: &[],
+    },
+    Precompile {
+        address: 0x03,
+        name: "ripemd160",
+        fork: Fork::Frontier,
+        eip: None,
+        gas: "600, +120 per word",
+        repricings: &[],
+    },
+    Precompile {
+        address: 0x04,
+        name: "identity",
+        fork: Fork::Frontier,
+        eip: None,
+        gas: "15, +3 per word",
+        repricings: &[],
+    },
+    Precompile {
+        address: 0x05,
+        name: "modexp",
+        fork: Fork::Byzantium,
+        eip: Some(198),
+        gas: "max(200, multiplication complexity * iterations / 3)",
+        repricings: &[(
+            Fork::Berlin,
+            2565,
+            "multiplication complexity * iterations / 20 → max(200, .. / 3)",
+        )],
+    },
+    Precompile {
+        address: 0x06,
+        name: "ecAdd",
+        fork: Fork::Byzantium,
+        eip: Some(196),
+        gas: "150",
+        repricings: &[(Fork::Istanbul, 1108, "500 → 150")],
+    },
+    Precompile {
+        address: 0x07,
+        name: "ecMul",
+        fork: Fork::Byzantium,
+        eip: Some(196),
+        gas: "6000",
+        repricings: &[(Fork::Istanbul, 1108, "40000 → 6000")],
+    },
+    Precompile {
+        address: 0x08,
+        name: "ecPairing",
+        fork: Fork::Byzantium,
+        eip: Some(197),
+        gas: "45000, +34000 per pair",
+        repricings: &[(
+            Fork::Istanbul,
+            1108,
+            "100000, +80000 per pair → 45000, +34000 per pair",
+        )],
+    },
+    Precompile {
+        address: 0x09,
+        name: "blake2f",
+        fork: Fork::Istanbul,
+        eip: Some(152),
+        gas: "1 per round",
+        repricings: &[],
+    },
+    Precompile {
+        address: 0x0a,
+        name: "pointEvaluation",
+        fork: Fork::Cancun,
+        eip: Some(4844),
+        gas: "50000",
+        repricings: &[],
+    },
+];
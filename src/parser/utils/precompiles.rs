@@ -0,0 +1,211 @@
+use super::executor::GAS_LIMIT;
+use crate::parser::types::result::ParseResult;
+
+use alloy_core::primitives::{hex, U256};
+use revm::{
+    precompile::{u64_to_address, Precompiles},
+    primitives::Env,
+};
+use serde_json::{json, Value};
+
+pub const SHA256: u8 = 0x02;
+pub const RIPEMD160: u8 = 0x03;
+pub const IDENTITY: u8 = 0x04;
+pub const MODEXP: u8 = 0x05;
+pub const EC_ADD: u8 = 0x06;
+pub const EC_MUL: u8 = 0x07;
+pub const EC_PAIRING: u8 = 0x08;
+pub const BLAKE2F: u8 = 0x09;
+
+/// Runs a precompile as of Cancun (without the KZG point evaluation) on its raw input, and
+/// returns its output with the gas it used. Inputs costing more than a block fail, ie: a
+/// `blake2f` with billions of rounds.
+pub fn run(address: u8, input: &[u8]) -> Result<(Vec<u8>, u64), String> {
+    let precompile = Precompiles::cancun()
+        .get(&u64_to_address(address as u64))
+        .ok_or(format!("no precompile at 0x{:02x}", address))?;
+    let output = precompile
+        .call_ref(&input.to_vec().into(), GAS_LIMIT, &Env::default())
+        .map_err(|e| e.to_string())?;
+    Ok((output.bytes.to_vec(), output.gas_used))
+}
+
+/// Input of the hash precompiles: hex strings and literals are hashed as bytes (like
+/// `keccak256`), other strings as UTF-8 and computed numbers as 32-byte words.
+pub fn data(arg: &ParseResult) -> Result<Vec<u8>, String> {
+    match arg {
+        ParseResult::String(s) => match s.strip_prefix("0x").map(hex::decode) {
+            Some(Ok(bytes)) => Ok(bytes),
+            _ => Ok(s.as_bytes().to_vec()),
+        },
+        ParseResult::Value(u) => Ok(u.to_be_bytes::<32>().to_vec()),
+        _ => Err("expected bytes, a string or a number".to_string()),
+    }
+}
+
+/// Input of the alt_bn128 precompiles: the raw input as a single hex string, or 32-byte words
+/// given as numbers, possibly grouped in points, ie: `ec_add((x1, y1), (x2, y2))`.
+pub fn words(args: &[ParseResult]) -> Result<Vec<u8>, String> {
+    match args {
+        [ParseResult::String(input)] => {
+            hex::decode(input).map_err(|e| format!("invalid input: {}", e))
+        }
+        args => {
+            let mut input = Vec::new();
+            for arg in args {
+                match arg {
+                    ParseResult::Value(u) => input.extend(u.to_be_bytes::<32>()),
+                    ParseResult::String(s) => {
+                        let word = s.parse::<U256>().map_err(|e| format!("{}: {}", s, e))?;
+                        input.extend(word.to_be_bytes::<32>());
+                    }
+                    ParseResult::Array(items) => input.extend(words(items)?),
+                    _ => return Err("expected numbers or points".to_string()),
+                }
+            }
+            Ok(input)
+        }
+    }
+}
+
+/// Output of `ec_add` and `ec_mul`, as the `(x, y)` coordinates of the point.
+pub fn point(output: &[u8]) -> ParseResult {
+    output
+        .chunks(32)
+        .map(|word| U256::from_be_slice(word).into())
+        .collect::<Vec<ParseResult>>()
+        .into()
+}
+
+/// `modexp(base, exponent, modulus)`, with numbers as 32-byte words (as Solidity passes
+/// `uint256`s) and hex strings as they are, ie: 256-byte RSA moduli. Returns the result, as
+/// long as the modulus, and the gas cost (EIP-2565).
+pub fn modexp(
+    base: &ParseResult,
+    exponent: &ParseResult,
+    modulus: &ParseResult,
+) -> Result<Value, String> {
+    let (base, exponent, modulus) = (operand(base)?, operand(exponent)?, operand(modulus)?);
+    let mut input = Vec::new();
+    for operand in [&base, &exponent, &modulus] {
+        input.extend(U256::from(operand.len()).to_be_bytes::<32>());
+    }
+    input.extend(base);
+    input.extend(exponent);
+    input.extend(modulus);
+    modexp_raw(&input)
+}
+
+/// `modexp` of the raw precompile input: the lengths, then the base, exponent and modulus.
+pub fn modexp_raw(input: &[u8]) -> Result<Value, String> {
+    let (output, gas) = run(MODEXP, input)?;
+    Ok(json!({
+        "result": format!("0x{}", hex::encode(output)),
+        "gas": gas,
+    }))
+}
+
+fn operand(arg: &ParseResult) -> Result<Vec<u8>, String> {
+    match arg {
+        ParseResult::Value(u) => Ok(u.to_be_bytes::<32>().to_vec()),
+        ParseResult::String(s) => hex::decode(s).map_err(|e| format!("{}: {}", s, e)),
+        _ => Err("expected numbers or hex strings".to_string()),
+    }
+}
+
+/// Input of `blake2f` (EIP-152) from its parameters: the number of rounds, the state `h`
+/// (64 bytes), the message block `m` (128 bytes), the offset counters `t` (16 bytes, as
+/// little-endian words) and the final block flag `f`.
+pub fn blake2f_input(
+    rounds: &ParseResult,
+    h: &ParseResult,
+    m: &ParseResult,
+    t: &ParseResult,
+    f: &ParseResult,
+) -> Result<Vec<u8>, String> {
+    let rounds = match rounds {
+        ParseResult::Value(u) => *u,
+        ParseResult::String(s) => s.parse::<U256>().map_err(|e| format!("rounds: {}", e))?,
+        _ => return Err("rounds: expected a number".to_string()),
+    };
+    let rounds = u32::try_from(rounds).map_err(|e| format!("rounds: {}", e))?;
+    let mut input = rounds.to_be_bytes().to_vec();
+    for (name, arg, len) in [("h", h, 64), ("m", m, 128), ("t", t, 16)] {
+        let bytes = match arg {
+            ParseResult::String(s) => hex::decode(s).map_err(|e| format!("{}: {}", name, e))?,
+            _ => return Err(format!("{}: expected {} bytes", name, len)),
+        };
+        if bytes.len() != len {
+            return Err(format!("{}: expected {} bytes", name, len));
+        }
+        input.extend(bytes);
+    }
+    let f = match f {
+        ParseResult::Value(u) if *u <= U256::from(1) => u.to::<u8>(),
+        ParseResult::String(s) if s == "true" || s == "false" => (s == "true") as u8,
+        _ => return Err("f: expected a boolean".to_string()),
+    };
+    input.push(f);
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_string(s: &str) -> ParseResult {
+        ParseResult::String(s.to_string())
+    }
+
+    /// Parameters of the EIP-152 test vectors: the `abc` block of the BLAKE2b reference.
+    fn blake2f_abc(rounds: ParseResult, f: ParseResult) -> Result<Vec<u8>, String> {
+        blake2f_input(
+            &rounds,
+            &hex_string("0x48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b"),
+            &hex_string(&format!("0x616263{}", "00".repeat(125))),
+            &hex_string("0x03000000000000000000000000000000"),
+            &f,
+        )
+    }
+
+    #[test]
+    fn blake2f_eip152_vectors() {
+        // vector 4, without rounds
+        let input = blake2f_abc(U256::ZERO.into(), U256::from(1).into()).unwrap();
+        let (output, gas) = run(BLAKE2F, &input).unwrap();
+        assert_eq!(
+            hex::encode(output),
+            "08c9bcf367e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d282e6ad7f520e511f6c3e2b8c68059b9442be0454267ce079217e1319cde05b"
+        );
+        assert_eq!(gas, 0);
+
+        // vector 5, with the 12 rounds of BLAKE2b
+        let input = blake2f_abc(U256::from(12).into(), hex_string("true")).unwrap();
+        let (output, gas) = run(BLAKE2F, &input).unwrap();
+        assert_eq!(
+            hex::encode(output),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+        assert_eq!(gas, 12);
+    }
+
+    #[test]
+    fn blake2f_rounds_beyond_the_gas_limit() {
+        let input = blake2f_abc(hex_string("0xffffffff"), hex_string("true")).unwrap();
+        assert!(run(BLAKE2F, &input).is_err());
+        assert!(blake2f_abc(hex_string("0x100000000"), hex_string("true")).is_err());
+    }
+
+    #[test]
+    fn hash_and_identity() {
+        let (output, gas) = run(SHA256, &data(&hex_string("abc")).unwrap()).unwrap();
+        assert_eq!(
+            hex::encode(output),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(gas, 72);
+
+        let input = data(&hex_string("0x00ff")).unwrap();
+        assert_eq!(run(IDENTITY, &input).unwrap(), (vec![0x00, 0xff], 18));
+    }
+}
@@ -0,0 +1,287 @@
+use super::signatures;
+use crate::parser::types::abi::named_values_to_json;
+
+use alloy_core::primitives::{hex, U256};
+use alloy_dyn_abi::{DynSolType, DynSolValue, JsonAbiExt};
+use alloy_json_abi::{Error, JsonAbi};
+use serde_json::{json, Map, Value};
+
+/// `Error(string)`, used by `require(cond, "reason")` and `revert("reason")`.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// `Panic(uint256)`, used by the compiler for failed assertions and runtime checks.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Decodes revert data: `Error(string)` messages, `Panic(uint256)` codes and custom errors from
+/// the ABI (if any) or the signature database. Revert data wrapped in a `bytes` argument or in a
+/// non-UTF-8 `Error(string)`, as bubbled up by multicalls and try/catch blocks, is decoded as
+/// well and returned under `inner`.
+pub fn decode_revert(data: &[u8], abi: Option<&JsonAbi>) -> Result<Value, String> {
+    if data.is_empty() {
+        return Ok(json!({
+            "error": null,
+            "reason": "empty revert data: `revert()`, `require(cond)` without a message, or out of gas",
+        }));
+    }
+    if data.len() < 4 {
+        return Err("revert data is shorter than a selector".to_string());
+    }
+    let (selector, params) = data.split_at(4);
+    let mut result = Map::new();
+    match selector {
+        s if s == ERROR_SELECTOR => {
+            let reason = DynSolType::Tuple(vec![DynSolType::Bytes])
+                .abi_decode_params(params)
+                .map_err(|e| format!("invalid Error(string) data: {}", e))?;
+            let Some(DynSolValue::Bytes(reason)) = reason.as_tuple().and_then(|t| t.first()) else {
+                return Err("invalid Error(string) data".to_string());
+            };
+            result.insert("error".to_string(), json!("Error(string)"));
+            result.insert(
+                "selector".to_string(),
+                json!(format!("0x{}", hex::encode(selector))),
+            );
+            match (decode_inner(reason, abi), std::str::from_utf8(reason)) {
+                (None, Ok(reason)) => {
+                    result.insert("reason".to_string(), json!(reason));
+                }
+                (inner, _) => {
+                    result.insert(
+                        "reason".to_string(),
+                        json!(format!("0x{}", hex::encode(reason))),
+                    );
+                    if let Some(inner) = inner {
+                        result.insert("inner".to_string(), inner);
+                    }
+                }
+            }
+        }
+        s if s == PANIC_SELECTOR => {
+            let code = U256::try_from_be_slice(params)
+                .filter(|_| params.len() == 32)
+                .ok_or("invalid Panic(uint256) data")?;
+            result.insert("error".to_string(), json!("Panic(uint256)"));
+            result.insert(
+                "selector".to_string(),
+                json!(format!("0x{}", hex::encode(selector))),
+            );
+            result.insert("code".to_string(), json!(format!("0x{:02x}", code)));
+            result.insert("reason".to_string(), json!(panic_reason(code)));
+        }
+        _ => {
+            let errors: Vec<&Error> = match abi {
+                Some(abi) => abi.errors().collect(),
+                None => Vec::new(),
+            };
+            let decoded = errors
+                .into_iter()
+                .chain(signatures::errors(selector))
+                .filter(|error| error.selector().as_slice() == selector)
+                .find_map(|error| {
+                    let values = error.abi_decode_input(params, true).ok()?;
+                    Some((error, values))
+                });
+            let error = decoded.as_ref().map(|(error, _)| error.signature());
+            result.insert("error".to_string(), json!(error));
+            result.insert(
+                "selector".to_string(),
+                json!(format!("0x{}", hex::encode(selector))),
+            );
+            match decoded {
+                Some((error, values)) => {
+                    result.insert(
+                        "args".to_string(),
+                        named_values_to_json(&values, &error.inputs),
+                    );
+                    let inner = values.iter().find_map(|value| match value {
+                        DynSolValue::Bytes(bytes) => decode_inner(bytes, abi),
+                        _ => None,
+                    });
+                    if let Some(inner) = inner {
+                        result.insert("inner".to_string(), inner);
+                    }
+                }
+                None => {
+                    result.insert("reason".to_string(), json!("unknown custom error"));
+                    result.insert(
+                        "data".to_string(),
+                        json!(format!("0x{}", hex::encode(params))),
+                    );
+                }
+            }
+        }
+    }
+    Ok(Value::Object(result))
+}
+
+/// Decodes bubbled up revert data, if the bytes are a selector followed by whole words and
+/// the error is identified.
+fn decode_inner(bytes: &[u8], abi: Option<&JsonAbi>) -> Option<Value> {
+    if bytes.len() < 4 || !(bytes.len() - 4).is_multiple_of(32) {
+        return None;
+    }
+    decode_revert(bytes, abi)
+        .ok()
+        .filter(|inner| !inner["error"].is_null())
+}
+
+/// Human explanation of the compiler panic codes.
+fn panic_reason(code: U256) -> &'static str {
+    match u64::try_from(code).unwrap_or(u64::MAX) {
+        0x00 => "generic compiler inserted panic",
+        0x01 => "assertion failed: `assert(false)`",
+        0x11 => "arithmetic overflow or underflow outside of an `unchecked` block",
+        0x12 => "division or modulo by zero",
+        0x21 => "conversion of a value too big or negative into an enum",
+        0x22 => "access to an incorrectly encoded storage byte array",
+        0x31 => "`.pop()` on an empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "too much memory allocated, or an array too large",
+        0x51 => "call to a zero-initialized internal function variable",
+        _ => "unknown panic code",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(address: &str) -> DynSolValue {
+        DynSolType::Address.coerce_str(address).unwrap()
+    }
+
+    /// Revert data of an error: its selector followed by the encoded arguments.
+    fn revert_data(selector: [u8; 4], args: Vec<DynSolValue>) -> Vec<u8> {
+        let mut data = selector.to_vec();
+        data.extend(DynSolValue::Tuple(args).abi_encode_params());
+        data
+    }
+
+    fn error_string(reason: &str) -> Vec<u8> {
+        revert_data(
+            ERROR_SELECTOR,
+            vec![DynSolValue::String(reason.to_string())],
+        )
+    }
+
+    fn custom_error(sig: &str, args: Vec<DynSolValue>) -> Vec<u8> {
+        revert_data(Error::parse(sig).unwrap().selector().0, args)
+    }
+
+    #[test]
+    fn error_string_reason() {
+        let decoded = decode_revert(&error_string("insufficient balance"), None).unwrap();
+        assert_eq!(decoded["error"], "Error(string)");
+        assert_eq!(decoded["selector"], "0x08c379a0");
+        assert_eq!(decoded["reason"], "insufficient balance");
+        assert!(decoded.get("inner").is_none());
+    }
+
+    #[test]
+    fn panic_codes() {
+        let panic = |code: u64| {
+            revert_data(
+                PANIC_SELECTOR,
+                vec![DynSolValue::Uint(U256::from(code), 256)],
+            )
+        };
+        let decoded = decode_revert(&panic(0x11), None).unwrap();
+        assert_eq!(decoded["error"], "Panic(uint256)");
+        assert_eq!(decoded["code"], "0x11");
+        assert_eq!(
+            decoded["reason"],
+            "arithmetic overflow or underflow outside of an `unchecked` block"
+        );
+        assert_eq!(
+            decode_revert(&panic(0x32), None).unwrap()["reason"],
+            "array index out of bounds"
+        );
+        assert_eq!(
+            decode_revert(&panic(0x99), None).unwrap()["reason"],
+            "unknown panic code"
+        );
+        assert!(decode_revert(&panic(0x01)[..20], None).is_err());
+    }
+
+    #[test]
+    fn custom_error_from_the_abi() {
+        let abi = JsonAbi::parse(["error Unauthorized(address caller, uint8 role)"]).unwrap();
+        let data = custom_error(
+            "Unauthorized(address,uint8)",
+            vec![
+                address("0x00000000000000000000000000000000000000aa"),
+                DynSolValue::Uint(U256::from(3), 8),
+            ],
+        );
+        let decoded = decode_revert(&data, Some(&abi)).unwrap();
+        assert_eq!(decoded["error"], "Unauthorized(address,uint8)");
+        assert_eq!(
+            decoded["args"],
+            json!({
+                "address caller": "0x00000000000000000000000000000000000000AA",
+                "uint8 role": "3",
+            })
+        );
+
+        // without the ABI, the error isn't known
+        let decoded = decode_revert(&data, None).unwrap();
+        assert!(decoded["error"].is_null());
+        assert_eq!(decoded["reason"], "unknown custom error");
+    }
+
+    #[test]
+    fn custom_error_from_the_database() {
+        let data = custom_error(
+            "ERC20InsufficientBalance(address,uint256,uint256)",
+            vec![
+                address("0x0000000000000000000000000000000000000001"),
+                DynSolValue::Uint(U256::from(1), 256),
+                DynSolValue::Uint(U256::from(2), 256),
+            ],
+        );
+        let decoded = decode_revert(&data, None).unwrap();
+        assert_eq!(
+            decoded["error"],
+            "ERC20InsufficientBalance(address,uint256,uint256)"
+        );
+        assert_eq!(
+            decoded["args"],
+            json!({
+                "address sender": "0x0000000000000000000000000000000000000001",
+                "uint256 balance": "1",
+                "uint256 needed": "2",
+            })
+        );
+    }
+
+    #[test]
+    fn bubbled_revert_data() {
+        // an `Error(string)` wrapped in the `bytes` argument of a custom error
+        let data = custom_error(
+            "ExecutionFailed(uint256,bytes)",
+            vec![
+                DynSolValue::Uint(U256::from(2), 256),
+                DynSolValue::Bytes(error_string("STF")),
+            ],
+        );
+        let decoded = decode_revert(&data, None).unwrap();
+        assert_eq!(decoded["error"], "ExecutionFailed(uint256,bytes)");
+        assert_eq!(decoded["inner"]["error"], "Error(string)");
+        assert_eq!(decoded["inner"]["reason"], "STF");
+
+        // a custom error re-thrown as the (non-UTF-8) reason of an `Error(string)`
+        let inner = custom_error(
+            "ERC20InvalidSender(address)",
+            vec![address("0x0000000000000000000000000000000000000000")],
+        );
+        let data = revert_data(ERROR_SELECTOR, vec![DynSolValue::Bytes(inner.clone())]);
+        let decoded = decode_revert(&data, None).unwrap();
+        assert_eq!(decoded["reason"], format!("0x{}", hex::encode(&inner)));
+        assert_eq!(decoded["inner"]["error"], "ERC20InvalidSender(address)");
+    }
+
+    #[test]
+    fn empty_and_short_revert_data() {
+        assert!(decode_revert(&[], None).unwrap()["error"].is_null());
+        assert!(decode_revert(&[0x08, 0xc3], None).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use crate::parser::types::result::ParseResult;
+
+use alloy_core::primitives::{hex, U256};
+use alloy_rlp::{Encodable, Header};
+use serde_json::{json, Value};
+
+/// A decoded RLP item, annotated with its position in the original payload.
+#[derive(Debug, Clone)]
+pub struct RlpItem {
+    pub offset: usize,
+    pub header_len: usize,
+    pub payload_len: usize,
+    pub kind: RlpKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum RlpKind {
+    Bytes(Vec<u8>),
+    List(Vec<RlpItem>),
+}
+
+impl RlpItem {
+    /// Total encoded length of the item (header + payload).
+    pub fn len(&self) -> usize {
+        self.header_len + self.payload_len
+    }
+
+    pub fn bytes(&self) -> Option<&[u8]> {
+        match &self.kind {
+            RlpKind::Bytes(b) => Some(b),
+            RlpKind::List(_) => None,
+        }
+    }
+
+    pub fn list(&self) -> Option<&[RlpItem]> {
+        match &self.kind {
+            RlpKind::List(items) => Some(items),
+            RlpKind::Bytes(_) => None,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        match &self.kind {
+            RlpKind::Bytes(b) => json!({
+                "offset": self.offset,
+                "length": self.payload_len,
+                "bytes": format!("0x{}", hex::encode(b)),
+            }),
+            RlpKind::List(items) => json!({
+                "offset": self.offset,
+                "length": self.payload_len,
+                "list": items.iter().map(|i| i.to_json()).collect::<Vec<Value>>(),
+            }),
+        }
+    }
+}
+
+/// Converts a value into the raw bytes of an RLP string:
+/// - numbers are encoded as big-endian integers without leading zeros.
+/// - `0x` prefixed strings are treated as raw bytes, and must be valid hex.
+/// - any other string is encoded as UTF-8.
+pub fn to_rlp_bytes(value: &ParseResult) -> Result<Vec<u8>, String> {
+    match value {
+        ParseResult::Value(u) => Ok(u.to_be_bytes_trimmed_vec()),
+        ParseResult::Address(a) => Ok(a.to_vec()),
+        ParseResult::String(s) => str_to_bytes(s),
+        _ => Err(format!("cannot RLP encode {}", value.to_string())),
+    }
+}
+
+fn str_to_bytes(s: &str) -> Result<Vec<u8>, String> {
+    match s.strip_prefix("0x") {
+        Some(stripped) => {
+            let padded = if stripped.len() % 2 == 1 {
+                format!("0{}", stripped)
+            } else {
+                stripped.to_string()
+            };
+            hex::decode(padded).map_err(|e| format!("invalid hex {}: {}", s, e))
+        }
+        None => Ok(s.as_bytes().to_vec()),
+    }
+}
+
+fn encode_json(value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+    match value {
+        Value::Array(items) => {
+            let mut payload = Vec::new();
+            for item in items {
+                encode_json(item, &mut payload)?;
+            }
+            encode_list_payload(&payload, out);
+        }
+        Value::String(s) => str_to_bytes(s)?.as_slice().encode(out),
+        Value::Number(n) => match n.as_u64() {
+            Some(n) => U256::from(n)
+                .to_be_bytes_trimmed_vec()
+                .as_slice()
+                .encode(out),
+            None => return Err(format!("cannot RLP encode {}", n)),
+        },
+        _ => return Err(format!("cannot RLP encode {}", value)),
+    }
+    Ok(())
+}
+
+/// Prepends an RLP list header to an already encoded list payload.
+pub fn encode_list_payload(payload: &[u8], out: &mut Vec<u8>) {
+    Header {
+        list: true,
+        payload_length: payload.len(),
+    }
+    .encode(out);
+    out.extend_from_slice(payload);
+}
+
+/// RLP encodes strings, numbers and (nested) arrays of them.
+pub fn rlp_encode(value: &ParseResult) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    match value {
+        ParseResult::Array(items) => {
+            let mut payload = Vec::new();
+            for item in items {
+                payload.extend(rlp_encode(item)?);
+            }
+            encode_list_payload(&payload, &mut out);
+        }
+        ParseResult::Json(json) => encode_json(json, &mut out)?,
+        _ => to_rlp_bytes(value)?.as_slice().encode(&mut out),
+    }
+    Ok(out)
+}
+
+/// Decodes a single RLP item starting at `buf[0]`, which sits at `offset` in the full payload.
+pub fn decode_item(buf: &[u8], offset: usize) -> Result<RlpItem, String> {
+    let mut cursor = buf;
+    let header = Header::decode(&mut cursor).map_err(|e| format!("offset {}: {}", offset, e))?;
+    let header_len = buf.len() - cursor.len();
+    if cursor.len() < header.payload_length {
+        return Err(format!("offset {}: item exceeds input length", offset));
+    }
+    let payload = &cursor[..header.payload_length];
+    let kind = if header.list {
+        let mut items = Vec::new();
+        let mut pos = 0;
+        while pos < payload.len() {
+            let item = decode_item(&payload[pos..], offset + header_len + pos)?;
+            pos += item.len();
+            items.push(item);
+        }
+        RlpKind::List(items)
+    } else {
+        RlpKind::Bytes(payload.to_vec())
+    };
+    Ok(RlpItem {
+        offset,
+        header_len,
+        payload_len: header.payload_length,
+        kind,
+    })
+}
+
+/// Decodes a hex encoded RLP payload, which must contain exactly one item.
+pub fn rlp_decode(input: &str) -> Result<RlpItem, String> {
+    let bytes = hex::decode(input).map_err(|e| format!("invalid hex: {}", e))?;
+    let item = decode_item(&bytes, 0)?;
+    if item.len() != bytes.len() {
+        return Err(format!(
+            "trailing bytes after offset {}: {}",
+            item.len(),
+            bytes.len() - item.len()
+        ));
+    }
+    Ok(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(s: &str) -> ParseResult {
+        ParseResult::String(s.to_string())
+    }
+
+    fn encode(value: ParseResult) -> String {
+        format!("0x{}", hex::encode(rlp_encode(&value).unwrap()))
+    }
+
+    #[test]
+    fn encode_canonical_vectors() {
+        assert_eq!(encode(string("dog")), "0x83646f67");
+        assert_eq!(
+            encode(ParseResult::Array(vec![string("cat"), string("dog")])),
+            "0xc88363617483646f67"
+        );
+        assert_eq!(encode(string("")), "0x80");
+        assert_eq!(encode(U256::ZERO.into()), "0x80");
+        assert_eq!(encode(U256::from(15).into()), "0x0f");
+        assert_eq!(encode(U256::from(1024).into()), "0x820400");
+        assert_eq!(encode(ParseResult::Array(vec![])), "0xc0");
+        assert_eq!(encode(string("0x0001")), "0x820001");
+        assert_eq!(
+            encode(ParseResult::Json(json!(["cat", ["dog", 1024]]))),
+            "0xcc83636174c783646f67820400"
+        );
+    }
+
+    #[test]
+    fn encode_rejects_invalid_hex() {
+        assert!(rlp_encode(&string("0xzz")).is_err());
+        assert!(rlp_encode(&ParseResult::Json(json!(["0xzz"]))).is_err());
+    }
+
+    #[test]
+    fn decode_nested_lists() {
+        let item = rlp_decode("0xc88363617483646f67").unwrap();
+        let items = item.list().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].bytes().unwrap(), b"cat");
+        assert_eq!(items[1].offset, 5);
+        assert_eq!(items[1].bytes().unwrap(), b"dog");
+        assert_eq!(rlp_decode("0xc0").unwrap().list().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn decode_rejections() {
+        // a single byte below 0x80 is its own encoding
+        assert!(rlp_decode("0x8100").is_err());
+        assert!(rlp_decode("0xb9ffff").is_err());
+        assert!(rlp_decode("0xc3836361").is_err());
+        assert_eq!(
+            rlp_decode("0x83646f6700").unwrap_err(),
+            "trailing bytes after offset 4: 1"
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use super::split_top_level;
+use crate::parser::types::{abi::parse_json_abi, result::ParseResult};
+
+use alloy_core::primitives::{hex, utils::keccak256};
+use alloy_dyn_abi::DynSolType;
+use alloy_json_abi::Function;
+use serde_json::{json, Value};
+
+/// 4-byte selector of a function signature, hashed in its canonical form (see
+/// [`canonical_signature`]). Signatures that can't be parsed are an error rather than hashed
+/// as written, which would give the selector of another function.
+pub fn selector(sig: &str) -> Result<[u8; 4], String> {
+    let hash = keccak256(canonical_signature(sig)?);
+    Ok([hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// Canonical form of a signature, as hashed for its selector: without the parameter names,
+/// data locations and modifiers, and with the type aliases expanded, ie:
+/// `function transfer(address to, uint amount) external returns (bool)` is
+/// `transfer(address,uint256)` and `fill((address maker, uint amount) memory order)` is
+/// `fill((address,uint256))`.
+pub fn canonical_signature(sig: &str) -> Result<String, String> {
+    let sig = sig.trim();
+    let sig = ["function ", "event ", "error "]
+        .iter()
+        .find_map(|keyword| sig.strip_prefix(keyword))
+        .unwrap_or(sig);
+    let (name, rest) = sig
+        .split_once('(')
+        .ok_or(format!("invalid signature: {}", sig))?;
+    let end = closing_paren(rest).ok_or(format!("unbalanced parentheses: {}", sig))?;
+    // the parser only accepts the names of top-level parameters
+    let sig = format!("{}({})", name.trim(), param_types(&rest[..end]));
+    let function =
+        Function::parse(&sig).map_err(|e| format!("invalid signature {}: {}", sig, e))?;
+    // the parser takes unknown types for user-defined ones, ie: `uint257` or a struct name
+    for param in &function.inputs {
+        DynSolType::parse(&param.selector_type())
+            .map_err(|e| format!("invalid type {}: {}", param.selector_type(), e))?;
+    }
+    Ok(function.signature())
+}
+
+/// Types of a list of parameters, with the tuple components stripped of their names as well.
+fn param_types(params: &str) -> String {
+    split_top_level(params)
+        .iter()
+        .filter(|param| !param.is_empty())
+        .map(|param| {
+            let tuple = param.strip_prefix("tuple").unwrap_or(param).trim_start();
+            match tuple.strip_prefix('(') {
+                Some(rest) => {
+                    let end = closing_paren(rest).unwrap_or(rest.len());
+                    let dims: String = rest[end..]
+                        .chars()
+                        .skip(1)
+                        .take_while(|c| *c == '[' || *c == ']' || c.is_ascii_digit())
+                        .collect();
+                    format!("({}){}", param_types(&rest[..end]), dims)
+                }
+                // ie: `address payable to` or `bytes calldata data`
+                None => param
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Index of the parenthesis closing the one just before `input`.
+fn closing_paren(input: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(i),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// ERC-165 interface identifier: the XOR of the selectors of the functions of an interface.
+/// Takes the signatures, or a single ABI block whose functions all belong to the interface.
+pub fn interface_id(args: &[ParseResult]) -> Result<String, String> {
+    let id = function_signatures(args)?
+        .iter()
+        .map(|sig| selector(sig))
+        .collect::<Result<Vec<_>, String>>()?
+        .into_iter()
+        .fold([0u8; 4], |id, selector| {
+            [
+                id[0] ^ selector[0],
+                id[1] ^ selector[1],
+                id[2] ^ selector[2],
+                id[3] ^ selector[3],
+            ]
+        });
+    Ok(format!("0x{}", hex::encode(id)))
+}
+
+/// Functions of two ABIs sharing a selector, ie: the functions of a transparent proxy that
+/// shadow those of its implementation. Returns `[{"selector", "a", "b"}]`.
+pub fn selector_collisions(a: &ParseResult, b: &ParseResult) -> Result<Value, String> {
+    let selectors = |abi: &ParseResult| {
+        function_signatures(std::slice::from_ref(abi))?
+            .into_iter()
+            .map(|sig| selector(&sig).map(|selector| (sig, selector)))
+            .collect::<Result<Vec<_>, String>>()
+    };
+    let (a, b) = (selectors(a)?, selectors(b)?);
+    let collisions = a
+        .iter()
+        .flat_map(|(sig_a, selector_a)| {
+            b.iter()
+                .filter(move |(_, selector_b)| selector_a == selector_b)
+                .map(move |(sig_b, _)| {
+                    json!({
+                        "selector": format!("0x{}", hex::encode(selector_a)),
+                        "a": sig_a,
+                        "b": sig_b,
+                    })
+                })
+        })
+        .collect();
+    Ok(Value::Array(collisions))
+}
+
+/// Signatures of the functions of a JSON or human-readable ABI block, or the given signatures
+/// (possibly as a list).
+fn function_signatures(args: &[ParseResult]) -> Result<Vec<String>, String> {
+    match args {
+        [abi @ ParseResult::Json(_)] => {
+            let abi = parse_json_abi(abi)?;
+            Ok(abi.functions().map(|f| f.signature()).collect())
+        }
+        [] => Err("expected function signatures or an ABI".to_string()),
+        args => args
+            .iter()
+            .flat_map(|arg| match arg {
+                ParseResult::Array(items) => items.clone(),
+                arg => vec![arg.clone()],
+            })
+            .map(|sig| {
+                sig.get_string()
+                    .ok_or("expected function signatures or an ABI".to_string())
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selector_hex(sig: &str) -> String {
+        format!("0x{}", hex::encode(selector(sig).unwrap()))
+    }
+
+    #[test]
+    fn selector_of_declarations() {
+        assert_eq!(selector_hex("transfer(address,uint256)"), "0xa9059cbb");
+        assert_eq!(
+            selector_hex("transfer(address to, uint amount)"),
+            "0xa9059cbb"
+        );
+        assert_eq!(
+            selector_hex("function transfer(address to, uint amount) external returns (bool)"),
+            "0xa9059cbb"
+        );
+    }
+
+    #[test]
+    fn canonical_signatures() {
+        for (sig, canonical) in [
+            (
+                "fill((address maker, uint amount) memory order)",
+                "fill((address,uint256))",
+            ),
+            (
+                "fill(tuple(address maker, uint[] amounts)[2] calldata orders, bytes32 salt)",
+                "fill((address,uint256[])[2],bytes32)",
+            ),
+            ("withdraw(address payable to)", "withdraw(address)"),
+            (
+                "f(bytes calldata data, string memory name, uint8[] storage ids)",
+                "f(bytes,string,uint8[])",
+            ),
+            (
+                "event Transfer(address indexed from, address indexed to, uint value)",
+                "Transfer(address,address,uint256)",
+            ),
+            ("error Unauthorized()", "Unauthorized()"),
+        ] {
+            assert_eq!(canonical_signature(sig).unwrap(), canonical, "{}", sig);
+        }
+    }
+
+    #[test]
+    fn invalid_signatures() {
+        for sig in [
+            "transfer",
+            "transfer(address to",
+            "transfer(address,uint257)",
+            "fill(Order order)",
+        ] {
+            assert!(selector(sig).is_err(), "{}", sig);
+        }
+    }
+
+    fn strings(sigs: &[&str]) -> ParseResult {
+        ParseResult::Array(sigs.iter().map(|sig| sig.to_string().into()).collect())
+    }
+
+    #[test]
+    fn erc165_interface_ids() {
+        assert_eq!(
+            interface_id(&[ParseResult::String("supportsInterface(bytes4)".to_string())]).unwrap(),
+            "0x01ffc9a7"
+        );
+        let erc721 = strings(&[
+            "balanceOf(address)",
+            "ownerOf(uint256)",
+            "safeTransferFrom(address,address,uint256,bytes)",
+            "safeTransferFrom(address,address,uint256)",
+            "transferFrom(address,address,uint256)",
+            "approve(address,uint256)",
+            "setApprovalForAll(address,bool)",
+            "getApproved(uint256)",
+            "isApprovedForAll(address,address)",
+        ]);
+        assert_eq!(interface_id(&[erc721]).unwrap(), "0x80ac58cd");
+        assert!(interface_id(&[]).is_err());
+    }
+
+    #[test]
+    fn collisions_between_abis() {
+        let proxy = ParseResult::Json(json!([
+            {
+                "type": "function",
+                "name": "collate_propagate_storage",
+                "inputs": [{ "name": "", "type": "bytes16" }],
+                "outputs": [],
+                "stateMutability": "nonpayable",
+            },
+            {
+                "type": "function",
+                "name": "upgradeTo",
+                "inputs": [{ "name": "implementation", "type": "address" }],
+                "outputs": [],
+                "stateMutability": "nonpayable",
+            },
+        ]));
+        let token = strings(&["burn(uint256)", "transfer(address,uint256)"]);
+        assert_eq!(
+            selector_collisions(&proxy, &token).unwrap(),
+            json!([{
+                "selector": "0x42966c68",
+                "a": "collate_propagate_storage(bytes16)",
+                "b": "burn(uint256)",
+            }])
+        );
+        let other = strings(&["mint(uint256)"]);
+        assert_eq!(selector_collisions(&token, &other).unwrap(), json!([]));
+    }
+}
@@ -0,0 +1,307 @@
+use crate::parser::types::result::ParseResult;
+
+use alloy_core::primitives::{hex, Address, Signature, B256, U256};
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use serde_json::{json, Value};
+
+/// Order of the secp256k1 curve.
+const SECP256K1_N: U256 = U256::from_limbs([
+    0xbfd25e8cd0364141,
+    0xbaaedce6af48a03b,
+    0xfffffffffffffffe,
+    0xffffffffffffffff,
+]);
+
+/// Functions that take a private key, and the position of the key in their arguments.
+/// Used to keep key material out of notebook exports.
+pub const KEY_FNS: &[(&str, usize)] = &[
+    ("sign", 1),
+    ("pubkey", 0),
+    ("public_key", 0),
+    ("address_of", 0),
+];
+
+/// Named arguments that hold a private key, ie: `tx(.., key=0x..)`.
+pub const KEY_ARGS: &[&str] = &["key", "private_key"];
+
+/// An ECDSA signature split into its components, with the parity normalized to a boolean.
+#[derive(Debug, Clone, Copy)]
+pub struct Sig {
+    pub r: U256,
+    pub s: U256,
+    pub y_parity: bool,
+}
+
+impl Sig {
+    /// Parses a 65-byte `r || s || v` signature (v being 0/1 or 27/28) or a 64-byte EIP-2098
+    /// compact signature.
+    pub fn parse(sig: &ParseResult) -> Result<Self, String> {
+        let bytes = match sig {
+            ParseResult::String(s) => {
+                hex::decode(s).map_err(|e| format!("invalid signature: {}", e))?
+            }
+            _ => return Err("signature must be a hex string".to_string()),
+        };
+        match bytes.len() {
+            65 => Ok(Self {
+                r: U256::from_be_slice(&bytes[..32]),
+                s: U256::from_be_slice(&bytes[32..64]),
+                y_parity: to_parity(U256::from(bytes[64]))?,
+            }),
+            64 => {
+                let y_parity_and_s = U256::from_be_slice(&bytes[32..]);
+                Ok(Self {
+                    r: U256::from_be_slice(&bytes[..32]),
+                    s: y_parity_and_s & (U256::MAX >> 1),
+                    y_parity: y_parity_and_s.bit(255),
+                })
+            }
+            n => Err(format!("invalid signature length: {} bytes", n)),
+        }
+    }
+
+    pub fn from_vrs(v: &ParseResult, r: &ParseResult, s: &ParseResult) -> Result<Self, String> {
+        match (v, r, s) {
+            (ParseResult::Value(v), ParseResult::Value(r), ParseResult::Value(s)) => Ok(Self {
+                r: *r,
+                s: *s,
+                y_parity: to_parity(*v)?,
+            }),
+            _ => Err("v, r and s must be numbers".to_string()),
+        }
+    }
+
+    /// Whether `s` is in the upper half of the curve order, which makes the signature malleable.
+    pub fn is_high_s(&self) -> bool {
+        self.s > SECP256K1_N >> 1
+    }
+
+    /// Returns the equivalent signature with a low `s` value (as required by EIP-2).
+    pub fn normalize_s(&self) -> Self {
+        if self.is_high_s() {
+            Self {
+                r: self.r,
+                s: SECP256K1_N - self.s,
+                y_parity: !self.y_parity,
+            }
+        } else {
+            *self
+        }
+    }
+
+    pub fn v(&self) -> u8 {
+        27 + self.y_parity as u8
+    }
+
+    /// 65-byte `r || s || v` encoding, with v as 27/28.
+    pub fn to_bytes(self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(65);
+        bytes.extend_from_slice(&self.r.to_be_bytes::<32>());
+        bytes.extend_from_slice(&self.s.to_be_bytes::<32>());
+        bytes.push(self.v());
+        bytes
+    }
+
+    /// 64-byte EIP-2098 encoding: `r || (y_parity << 255 | s)`. Only defined for low `s`.
+    pub fn to_compact(self) -> Result<Vec<u8>, String> {
+        if self.is_high_s() {
+            return Err("compact signatures require a low s value".to_string());
+        }
+        let mut y_parity_and_s = self.s;
+        y_parity_and_s.set_bit(255, self.y_parity);
+        let mut bytes = Vec::with_capacity(64);
+        bytes.extend_from_slice(&self.r.to_be_bytes::<32>());
+        bytes.extend_from_slice(&y_parity_and_s.to_be_bytes::<32>());
+        Ok(bytes)
+    }
+
+    /// Recovers the signer of a hash. High `s` values are accepted, like the `ecrecover`
+    /// precompile does.
+    pub fn recover(&self, hash: B256) -> Result<Address, String> {
+        let sig = self.normalize_s();
+        Signature::from_rs_and_parity(sig.r, sig.s, sig.y_parity)
+            .map_err(|e| e.to_string())?
+            .recover_address_from_prehash(&hash)
+            .map_err(|e| format!("failed to recover signer: {}", e))
+    }
+
+    pub fn to_json(self) -> Value {
+        json!({
+            "r": format!("0x{}", hex::encode(self.r.to_be_bytes::<32>())),
+            "s": format!("0x{}", hex::encode(self.s.to_be_bytes::<32>())),
+            "v": self.v(),
+            "yParity": self.y_parity as u8,
+            "highS": self.is_high_s(),
+            "compact": self.to_compact().map(|c| format!("0x{}", hex::encode(c))).ok(),
+        })
+    }
+}
+
+fn to_parity(v: U256) -> Result<bool, String> {
+    match u8::try_from(v) {
+        Ok(0) | Ok(27) => Ok(false),
+        Ok(1) | Ok(28) => Ok(true),
+        _ => Err(format!("invalid v value: {}", v)),
+    }
+}
+
+/// Parses a 32-byte value (ie: a hash), either as a number or as a hex string.
+pub fn to_b256(arg: &ParseResult) -> Result<B256, String> {
+    match arg {
+        ParseResult::Value(u) => Ok(B256::from(*u)),
+        ParseResult::String(s) => {
+            let bytes = hex::decode(s).map_err(|e| format!("invalid hex: {}", e))?;
+            if bytes.len() != 32 {
+                return Err("expected 32 bytes".to_string());
+            }
+            Ok(B256::from_slice(&bytes))
+        }
+        _ => Err("expected 32 bytes".to_string()),
+    }
+}
+
+/// Parses a 32-byte secp256k1 private key, either as a number or as a hex string.
+pub fn signing_key(key: &ParseResult) -> Result<SigningKey, String> {
+    let key = to_b256(key).map_err(|_| "private key must be 32 bytes".to_string())?;
+    SigningKey::from_slice(key.as_slice()).map_err(|e| format!("invalid key: {}", e))
+}
+
+/// Signs a 32-byte hash, without any prefix, returning the resulting signature.
+pub fn sign(hash: B256, key: &SigningKey) -> Result<Sig, String> {
+    let (sig, recovery_id) = key
+        .sign_prehash_recoverable(hash.as_slice())
+        .map_err(|e| format!("failed to sign: {}", e))?;
+    Ok(Sig {
+        r: U256::from_be_slice(&sig.r().to_bytes()),
+        s: U256::from_be_slice(&sig.s().to_bytes()),
+        y_parity: recovery_id.is_y_odd(),
+    })
+}
+
+/// Uncompressed SEC1 encoding of the public key of a private key (`0x04 || x || y`).
+pub fn pubkey(key: &SigningKey) -> Vec<u8> {
+    key.verifying_key()
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec()
+}
+
+/// Derives the address of a private key (32 bytes) or a public key (33, 64 or 65 bytes).
+pub fn address_of(arg: &ParseResult) -> Result<Address, String> {
+    let bytes = match arg {
+        ParseResult::Value(_) => return Ok(Address::from_private_key(&signing_key(arg)?)),
+        ParseResult::String(s) => hex::decode(s).map_err(|e| format!("invalid hex: {}", e))?,
+        _ => return Err("expected a private or public key".to_string()),
+    };
+    match bytes.len() {
+        32 => Ok(Address::from_private_key(&signing_key(arg)?)),
+        64 => Ok(Address::from_raw_public_key(&bytes)),
+        33 | 65 => VerifyingKey::from_sec1_bytes(&bytes)
+            .map(|key| Address::from_public_key(&key))
+            .map_err(|e| format!("invalid public key: {}", e)),
+        n => Err(format!("invalid key length: {} bytes", n)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_core::primitives::{address, b256, utils::eip191_hash_message};
+
+    /// Signing hash and key of the EIP-155 example transaction.
+    const EIP155_HASH: B256 =
+        b256!("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53");
+    const EIP155_KEY: &str = "0x4646464646464646464646464646464646464646464646464646464646464646";
+
+    fn eip155_sig() -> Sig {
+        Sig {
+            r: "18515461264373351373200002665853028612451056578545711640558177340181847433846"
+                .parse()
+                .unwrap(),
+            s: "46948507304638947509940763649030358759909902576025900602547168820602576006531"
+                .parse()
+                .unwrap(),
+            y_parity: false,
+        }
+    }
+
+    #[test]
+    fn sign_eip155_example() {
+        let key = signing_key(&EIP155_KEY.into()).unwrap();
+        let sig = sign(EIP155_HASH, &key).unwrap();
+        let expected = eip155_sig();
+        assert_eq!(
+            (sig.r, sig.s, sig.y_parity),
+            (expected.r, expected.s, false)
+        );
+    }
+
+    #[test]
+    fn recover_eip155_example() {
+        let signer = address!("9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+        assert_eq!(eip155_sig().recover(EIP155_HASH).unwrap(), signer);
+        assert_eq!(address_of(&EIP155_KEY.into()).unwrap(), signer);
+        // the malleable high-s twin recovers the same signer
+        let high_s = Sig {
+            r: eip155_sig().r,
+            s: SECP256K1_N - eip155_sig().s,
+            y_parity: true,
+        };
+        assert!(high_s.is_high_s());
+        assert_eq!(high_s.recover(EIP155_HASH).unwrap(), signer);
+    }
+
+    #[test]
+    fn eip2098_examples() {
+        let key = signing_key(
+            &"0x1234567890123456789012345678901234567890123456789012345678901234".into(),
+        )
+        .unwrap();
+        let sig = sign(eip191_hash_message("Hello World"), &key).unwrap();
+        assert_eq!(
+            hex::encode(sig.to_bytes()),
+            "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+             7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064\
+             1b"
+        );
+        assert_eq!(
+            hex::encode(sig.to_compact().unwrap()),
+            "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90\
+             7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064"
+        );
+
+        let sig = Sig::parse(
+            &"0x9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76\
+              139c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793\
+              1c"
+            .into(),
+        )
+        .unwrap();
+        let compact = sig.to_compact().unwrap();
+        assert_eq!(
+            hex::encode(&compact),
+            "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76\
+             939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793"
+        );
+        let parsed = Sig::parse(&format!("0x{}", hex::encode(compact)).into()).unwrap();
+        assert_eq!(parsed.to_bytes(), sig.to_bytes());
+    }
+
+    #[test]
+    fn public_key_of_one_is_the_generator() {
+        let key = signing_key(&ParseResult::Value(U256::from(1))).unwrap();
+        assert_eq!(
+            hex::encode(pubkey(&key)),
+            "04\
+             79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+             483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+        );
+        let signer = address!("7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+        assert_eq!(
+            address_of(&ParseResult::Value(U256::from(1))).unwrap(),
+            signer
+        );
+        let public_key = format!("0x{}", hex::encode(&pubkey(&key)[1..]));
+        assert_eq!(address_of(&public_key.into()).unwrap(), signer);
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use alloy_json_abi::{Error, Event, Function};
+
+/// Signature database compressed by `build.rs` from `signatures.txt`.
+const COMPRESSED: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/signatures.z"));
+
+lazy_static::lazy_static! {
+    static ref SIGNATURES: Signatures = Signatures::load();
+}
+
+/// Function selectors, event topic0 values and error selectors of well-known contracts, with
+/// every item sharing a selector kept as a candidate.
+#[derive(Default)]
+struct Signatures {
+    functions: HashMap<[u8; 4], Vec<Function>>,
+    events: HashMap<[u8; 32], Vec<Event>>,
+    errors: HashMap<[u8; 4], Vec<Error>>,
+}
+
+impl Signatures {
+    fn load() -> Self {
+        let mut signatures = Self::default();
+        let Ok(data) = miniz_oxide::inflate::decompress_to_vec_zlib(COMPRESSED) else {
+            return signatures;
+        };
+        for line in String::from_utf8_lossy(&data).lines() {
+            match line.split_once(' ') {
+                Some(("function", _)) => {
+                    if let Ok(f) = Function::parse(line) {
+                        signatures
+                            .functions
+                            .entry(f.selector().0)
+                            .or_default()
+                            .push(f);
+                    }
+                }
+                Some(("event", _)) => {
+                    if let Ok(e) = Event::parse(line) {
+                        signatures.events.entry(e.selector().0).or_default().push(e);
+                    }
+                }
+                Some(("error", _)) => {
+                    if let Ok(e) = Error::parse(line) {
+                        signatures.errors.entry(e.selector().0).or_default().push(e);
+                    }
+                }
+                _ => {}
+            }
+        }
+        signatures
+    }
+}
+
+/// Functions matching a 4-byte selector.
+pub fn functions(selector: &[u8]) -> &'static [Function] {
+    selector
+        .try_into()
+        .ok()
+        .and_then(|selector: [u8; 4]| SIGNATURES.functions.get(&selector))
+        .map_or(&[], Vec::as_slice)
+}
+
+/// Events matching a topic0.
+pub fn events(topic0: &[u8]) -> &'static [Event] {
+    topic0
+        .try_into()
+        .ok()
+        .and_then(|topic0: [u8; 32]| SIGNATURES.events.get(&topic0))
+        .map_or(&[], Vec::as_slice)
+}
+
+/// Custom errors matching a 4-byte selector.
+pub fn errors(selector: &[u8]) -> &'static [Error] {
+    selector
+        .try_into()
+        .ok()
+        .and_then(|selector: [u8; 4]| SIGNATURES.errors.get(&selector))
+        .map_or(&[], Vec::as_slice)
+}
+
+/// Canonical signatures of the candidates, deduplicated (ie: ERC-20 and ERC-721 `Transfer`
+/// events only differ by their indexed parameters).
+pub fn candidates(signatures: impl Iterator<Item = String>) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+    for signature in signatures {
+        if !candidates.contains(&signature) {
+            candidates.push(signature);
+        }
+    }
+    candidates
+}
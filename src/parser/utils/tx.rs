@@ -0,0 +1,647 @@
+use super::{
+    rlp::{decode_item, encode_list_payload, RlpItem},
+    signature::{sign, signing_key, Sig},
+    u256_to_address,
+};
+use crate::parser::types::{abi::abi_decode_to_json, result::ParseResult};
+
+use alloy_core::primitives::{hex, keccak256, Address, B256, U256};
+use alloy_rlp::Encodable;
+use serde_json::{json, Map, Value};
+
+const LEGACY: &[&str] = &[
+    "nonce", "gasPrice", "gas", "to", "value", "input", "v", "r", "s",
+];
+const EIP2930: &[&str] = &[
+    "chainId",
+    "nonce",
+    "gasPrice",
+    "gas",
+    "to",
+    "value",
+    "input",
+    "accessList",
+    "yParity",
+    "r",
+    "s",
+];
+const EIP1559: &[&str] = &[
+    "chainId",
+    "nonce",
+    "maxPriorityFeePerGas",
+    "maxFeePerGas",
+    "gas",
+    "to",
+    "value",
+    "input",
+    "accessList",
+    "yParity",
+    "r",
+    "s",
+];
+const EIP4844: &[&str] = &[
+    "chainId",
+    "nonce",
+    "maxPriorityFeePerGas",
+    "maxFeePerGas",
+    "gas",
+    "to",
+    "value",
+    "input",
+    "accessList",
+    "maxFeePerBlobGas",
+    "blobVersionedHashes",
+    "yParity",
+    "r",
+    "s",
+];
+const EIP7702: &[&str] = &[
+    "chainId",
+    "nonce",
+    "maxPriorityFeePerGas",
+    "maxFeePerGas",
+    "gas",
+    "to",
+    "value",
+    "input",
+    "accessList",
+    "authorizationList",
+    "yParity",
+    "r",
+    "s",
+];
+
+/// Magic byte prefixed to EIP-7702 authorization tuples before hashing.
+const AUTH_MAGIC: u8 = 0x05;
+
+/// Decodes a signed raw transaction (legacy or typed envelope) into a json object, recovering
+/// the sender and computing the transaction hash. If a function signature is supplied, the
+/// calldata is decoded as well, with decoding errors reported under `decodedInput`.
+pub fn decode_tx(raw: &str, sig: Option<&str>) -> Result<Value, String> {
+    let bytes = hex::decode(raw).map_err(|e| format!("invalid hex: {}", e))?;
+    let first = *bytes.first().ok_or("empty transaction")?;
+    let (tx_type, start) = match first {
+        0xc0..=0xff => (None, 0),
+        0x00..=0x7f => (Some(first), 1),
+        _ => return Err(format!("invalid transaction type: {:#04x}", first)),
+    };
+
+    let envelope = decode_item(&bytes[start..], start)?;
+    if start + envelope.len() != bytes.len() {
+        return Err(format!(
+            "trailing bytes after offset {}",
+            start + envelope.len()
+        ));
+    }
+    // EIP-4844 network form: [tx_payload_body, blobs, commitments, proofs]
+    let tx = match (tx_type, envelope.list()) {
+        (Some(3), Some([inner, ..])) if inner.list().is_some() => inner.clone(),
+        _ => envelope,
+    };
+    let fields = tx.list().ok_or("transaction is not an RLP list")?;
+    let names = match tx_type {
+        None => LEGACY,
+        Some(1) => EIP2930,
+        Some(2) => EIP1559,
+        Some(3) => EIP4844,
+        Some(4) => EIP7702,
+        Some(t) => return Err(format!("unsupported transaction type: {}", t)),
+    };
+    if fields.len() != names.len() {
+        return Err(format!(
+            "expected {} fields, found {}",
+            names.len(),
+            fields.len()
+        ));
+    }
+
+    let (unsigned, signature) = fields.split_at(fields.len() - 3);
+    let r = to_u256(&signature[1])?;
+    let s = to_u256(&signature[2])?;
+    let v = to_u256(&signature[0])?;
+
+    let mut tx_json = Map::new();
+    tx_json.insert("type".to_string(), json!(tx_type.unwrap_or(0)));
+    let (signing_hash, parity) = match tx_type {
+        None => {
+            let v: u64 = v.try_into().map_err(|_| "invalid v value".to_string())?;
+            match v {
+                27 | 28 => (keccak256(encode_list(unsigned, &bytes, &[])), v == 28),
+                35.. => {
+                    let chain_id = (v - 35) / 2;
+                    let mut extra = Vec::new();
+                    chain_id.encode(&mut extra);
+                    0u8.encode(&mut extra);
+                    0u8.encode(&mut extra);
+                    tx_json.insert("chainId".to_string(), json!(chain_id));
+                    (
+                        keccak256(encode_list(unsigned, &bytes, &extra)),
+                        (v - 35) % 2 == 1,
+                    )
+                }
+                _ => return Err(format!("invalid v value: {}", v)),
+            }
+        }
+        Some(t) => {
+            let mut payload = vec![t];
+            payload.extend(encode_list(unsigned, &bytes, &[]));
+            (keccak256(payload), to_parity(v)?)
+        }
+    };
+
+    for (name, item) in names.iter().zip(fields) {
+        tx_json.insert(name.to_string(), field_to_json(name, item, &bytes)?);
+    }
+
+    let mut raw_tx = tx_type.map(|t| vec![t]).unwrap_or_default();
+    raw_tx.extend_from_slice(raw_slice(&tx, &bytes));
+    tx_json.insert("hash".to_string(), json!(keccak256(raw_tx).to_string()));
+    let from = recover(signing_hash, parity, r, s)?;
+    tx_json.insert("from".to_string(), json!(from.to_checksum(None)));
+
+    if let Some(sig) = sig {
+        let input = fields[names.iter().position(|n| *n == "input").unwrap()]
+            .bytes()
+            .unwrap_or_default();
+        let decoded =
+            abi_decode_to_json(sig, &hex::encode(input)).unwrap_or_else(|e| json!({ "error": e }));
+        tx_json.insert("decodedInput".to_string(), decoded);
+    }
+    Ok(Value::Object(tx_json))
+}
+
+/// EIP-7702 authorization signing hash: `keccak256(0x05 || rlp([chain_id, address, nonce]))`.
+pub fn auth_hash(chain_id: U256, address: Address, nonce: U256) -> B256 {
+    let mut fields = Vec::new();
+    encode_uint(chain_id, &mut fields);
+    address.as_slice().encode(&mut fields);
+    encode_uint(nonce, &mut fields);
+    let mut payload = vec![AUTH_MAGIC];
+    encode_list_payload(&fields, &mut payload);
+    keccak256(payload)
+}
+
+/// Builds an EIP-1559 (or legacy, when `gas_price` is given) transaction from named fields,
+/// returning its unsigned RLP encoding and signing hash. When a private key is supplied, the
+/// signed raw transaction is returned as well.
+pub fn build_tx(fields: &[(String, ParseResult)]) -> Result<Value, String> {
+    let mut tx_type = None;
+    let mut chain_id = None;
+    let mut nonce = U256::ZERO;
+    let mut gas = None;
+    let mut gas_price = None;
+    let mut max_fee = U256::ZERO;
+    let mut priority_fee = U256::ZERO;
+    let mut to = None;
+    let mut value = U256::ZERO;
+    let mut data = Vec::new();
+    let mut key = None;
+    for (name, arg) in fields {
+        match name.as_str() {
+            "type" => tx_type = Some(to_uint(name, arg)?),
+            "chain_id" | "chain" => chain_id = Some(to_uint(name, arg)?),
+            "nonce" => nonce = to_uint(name, arg)?,
+            "gas" | "gas_limit" => gas = Some(to_uint(name, arg)?),
+            "gas_price" => gas_price = Some(to_uint(name, arg)?),
+            "max_fee" | "max_fee_per_gas" => max_fee = to_uint(name, arg)?,
+            "priority_fee" | "max_priority_fee_per_gas" => priority_fee = to_uint(name, arg)?,
+            "to" => to = Some(to_tx_address(arg)?),
+            "value" => value = to_uint(name, arg)?,
+            "data" | "input" => data = arg.get_bytes().map_err(|e| format!("data: {}", e))?,
+            "key" | "private_key" => key = Some(signing_key(arg)?),
+            _ => return Err(format!("unknown transaction field: {}", name)),
+        }
+    }
+    let gas = gas.unwrap_or_else(|| U256::from(intrinsic_gas(&data, to.is_none())));
+    let legacy = match tx_type.map(u64::try_from) {
+        None => gas_price.is_some(),
+        Some(Ok(0)) => true,
+        Some(Ok(2)) => false,
+        _ => return Err("only legacy (0) and EIP-1559 (2) transactions are supported".into()),
+    };
+
+    let mut tx_json = Map::new();
+    let mut fields = Vec::new();
+    if legacy {
+        tx_json.insert("type".to_string(), json!(0));
+        if let Some(chain_id) = chain_id {
+            tx_json.insert("chainId".to_string(), u256_to_json(chain_id));
+        }
+        let gas_price = gas_price.unwrap_or_default();
+        tx_json.insert("nonce".to_string(), u256_to_json(nonce));
+        tx_json.insert("gasPrice".to_string(), u256_to_json(gas_price));
+        encode_uint(nonce, &mut fields);
+        encode_uint(gas_price, &mut fields);
+    } else {
+        let chain_id = chain_id.ok_or("chain_id is required for EIP-1559 transactions")?;
+        tx_json.insert("type".to_string(), json!(2));
+        tx_json.insert("chainId".to_string(), u256_to_json(chain_id));
+        tx_json.insert("nonce".to_string(), u256_to_json(nonce));
+        tx_json.insert(
+            "maxPriorityFeePerGas".to_string(),
+            u256_to_json(priority_fee),
+        );
+        tx_json.insert("maxFeePerGas".to_string(), u256_to_json(max_fee));
+        encode_uint(chain_id, &mut fields);
+        encode_uint(nonce, &mut fields);
+        encode_uint(priority_fee, &mut fields);
+        encode_uint(max_fee, &mut fields);
+    }
+    tx_json.insert("gas".to_string(), u256_to_json(gas));
+    tx_json.insert(
+        "to".to_string(),
+        to.map_or(Value::Null, |to| json!(to.to_checksum(None))),
+    );
+    tx_json.insert("value".to_string(), u256_to_json(value));
+    tx_json.insert(
+        "input".to_string(),
+        json!(format!("0x{}", hex::encode(&data))),
+    );
+    encode_uint(gas, &mut fields);
+    to.map_or(Vec::new(), |to| to.to_vec())
+        .as_slice()
+        .encode(&mut fields);
+    encode_uint(value, &mut fields);
+    data.as_slice().encode(&mut fields);
+    if !legacy {
+        // empty access list
+        encode_list_payload(&[], &mut fields);
+        tx_json.insert("accessList".to_string(), json!([]));
+    }
+
+    let prefix = if legacy { vec![] } else { vec![2u8] };
+    let mut unsigned = fields.clone();
+    if let (true, Some(chain_id)) = (legacy, chain_id) {
+        encode_uint(chain_id, &mut unsigned);
+        0u8.encode(&mut unsigned);
+        0u8.encode(&mut unsigned);
+    }
+    let mut unsigned_tx = prefix.clone();
+    encode_list_payload(&unsigned, &mut unsigned_tx);
+    let signing_hash = keccak256(&unsigned_tx);
+    tx_json.insert(
+        "unsignedTx".to_string(),
+        json!(format!("0x{}", hex::encode(&unsigned_tx))),
+    );
+    tx_json.insert("signingHash".to_string(), json!(signing_hash.to_string()));
+
+    if let Some(key) = key {
+        let sig = sign(signing_hash, &key)?;
+        let parity = U256::from(sig.y_parity as u64);
+        let v = match (legacy, chain_id) {
+            (true, Some(chain_id)) => chain_id * U256::from(2) + U256::from(35) + parity,
+            (true, None) => U256::from(27) + parity,
+            (false, _) => parity,
+        };
+        encode_uint(v, &mut fields);
+        encode_uint(sig.r, &mut fields);
+        encode_uint(sig.s, &mut fields);
+        let mut signed_tx = prefix;
+        encode_list_payload(&fields, &mut signed_tx);
+        tx_json.insert(
+            "from".to_string(),
+            json!(Address::from_private_key(&key).to_checksum(None)),
+        );
+        tx_json.insert(
+            "signedTx".to_string(),
+            json!(format!("0x{}", hex::encode(&signed_tx))),
+        );
+        tx_json.insert("hash".to_string(), json!(keccak256(&signed_tx).to_string()));
+    }
+    Ok(Value::Object(tx_json))
+}
+
+fn to_uint(name: &str, arg: &ParseResult) -> Result<U256, String> {
+    match arg {
+        ParseResult::Value(u) => Ok(*u),
+        ParseResult::String(s) => s.parse::<U256>().map_err(|e| format!("{}: {}", name, e)),
+        _ => Err(format!("{}: expected a number", name)),
+    }
+}
+
+fn to_tx_address(arg: &ParseResult) -> Result<Address, String> {
+    match arg {
+        ParseResult::Address(a) => Ok(*a),
+        ParseResult::Value(u) => Ok(u256_to_address(*u)),
+        ParseResult::String(s) => s.parse::<Address>().map_err(|e| format!("to: {}", e)),
+        _ => Err("to: expected an address".to_string()),
+    }
+}
+
+/// Gas a transaction uses before executing anything: 21000, plus 4 gas per zero byte and 16
+/// per other byte of data, plus 32000 and 2 gas per initcode word for contract creations
+/// (EIP-2028, EIP-2 and EIP-3860).
+fn intrinsic_gas(data: &[u8], create: bool) -> u64 {
+    let zeros = data.iter().filter(|byte| **byte == 0).count() as u64;
+    let mut gas = 21000 + 4 * zeros + 16 * (data.len() as u64 - zeros);
+    if create {
+        gas += 32000 + 2 * (data.len() as u64).div_ceil(32);
+    }
+    gas
+}
+
+fn encode_uint(u: U256, out: &mut Vec<u8>) {
+    u.to_be_bytes_trimmed_vec().as_slice().encode(out);
+}
+
+fn raw_slice<'a>(item: &RlpItem, bytes: &'a [u8]) -> &'a [u8] {
+    &bytes[item.offset..item.offset + item.len()]
+}
+
+/// Re-encodes the given items as an RLP list, reusing their original encoding.
+fn encode_list(items: &[RlpItem], bytes: &[u8], extra: &[u8]) -> Vec<u8> {
+    let mut payload = Vec::new();
+    for item in items {
+        payload.extend_from_slice(raw_slice(item, bytes));
+    }
+    payload.extend_from_slice(extra);
+    let mut out = Vec::new();
+    encode_list_payload(&payload, &mut out);
+    out
+}
+
+fn expect_bytes(item: &RlpItem) -> Result<&[u8], String> {
+    item.bytes()
+        .ok_or_else(|| format!("offset {}: expected bytes, found a list", item.offset))
+}
+
+fn expect_list(item: &RlpItem) -> Result<&[RlpItem], String> {
+    item.list()
+        .ok_or_else(|| format!("offset {}: expected a list, found bytes", item.offset))
+}
+
+fn to_u256(item: &RlpItem) -> Result<U256, String> {
+    U256::try_from_be_slice(expect_bytes(item)?)
+        .ok_or_else(|| format!("offset {}: value exceeds 256 bits", item.offset))
+}
+
+fn to_parity(v: U256) -> Result<bool, String> {
+    match u8::try_from(v) {
+        Ok(0) => Ok(false),
+        Ok(1) => Ok(true),
+        _ => Err(format!("invalid y parity: {}", v)),
+    }
+}
+
+fn to_address(item: &RlpItem) -> Result<Address, String> {
+    let b = expect_bytes(item)?;
+    if b.len() != 20 {
+        return Err(format!("offset {}: invalid address length", item.offset));
+    }
+    Ok(Address::from_slice(b))
+}
+
+fn to_b256(item: &RlpItem) -> Result<B256, String> {
+    let b = expect_bytes(item)?;
+    if b.len() != 32 {
+        return Err(format!("offset {}: invalid hash length", item.offset));
+    }
+    Ok(B256::from_slice(b))
+}
+
+/// Quantities are shown as numbers when they fit in a u64, otherwise as decimal strings.
+fn quantity(item: &RlpItem) -> Result<Value, String> {
+    Ok(u256_to_json(to_u256(item)?))
+}
+
+fn u256_to_json(value: U256) -> Value {
+    match u64::try_from(value) {
+        Ok(v) => json!(v),
+        Err(_) => json!(value.to_string()),
+    }
+}
+
+fn recover(hash: B256, y_parity: bool, r: U256, s: U256) -> Result<Address, String> {
+    Sig { r, s, y_parity }.recover(hash)
+}
+
+fn field_to_json(name: &str, item: &RlpItem, bytes: &[u8]) -> Result<Value, String> {
+    match name {
+        "to" => match expect_bytes(item)? {
+            [] => Ok(Value::Null),
+            _ => Ok(json!(to_address(item)?.to_checksum(None))),
+        },
+        "input" => Ok(json!(format!("0x{}", hex::encode(expect_bytes(item)?)))),
+        "r" | "s" => Ok(json!(format!("{:#x}", to_u256(item)?))),
+        "accessList" => expect_list(item)?
+            .iter()
+            .map(|entry| match expect_list(entry)? {
+                [address, keys] => Ok(json!({
+                    "address": to_address(address)?.to_checksum(None),
+                    "storageKeys": expect_list(keys)?
+                        .iter()
+                        .map(|k| to_b256(k).map(|k| k.to_string()))
+                        .collect::<Result<Vec<_>, String>>()?,
+                })),
+                _ => Err(format!(
+                    "offset {}: invalid access list entry",
+                    entry.offset
+                )),
+            })
+            .collect::<Result<Vec<_>, String>>()
+            .map(Value::Array),
+        "blobVersionedHashes" => expect_list(item)?
+            .iter()
+            .map(|h| to_b256(h).map(|h| json!(h.to_string())))
+            .collect::<Result<Vec<_>, String>>()
+            .map(Value::Array),
+        "authorizationList" => expect_list(item)?
+            .iter()
+            .map(|auth| authorization_to_json(auth, bytes))
+            .collect::<Result<Vec<_>, String>>()
+            .map(Value::Array),
+        _ => quantity(item),
+    }
+}
+
+/// Decodes an EIP-7702 authorization tuple `[chain_id, address, nonce, y_parity, r, s]` and
+/// recovers its authority.
+fn authorization_to_json(auth: &RlpItem, bytes: &[u8]) -> Result<Value, String> {
+    let fields = expect_list(auth)?;
+    if fields.len() != 6 {
+        return Err(format!("offset {}: invalid authorization", auth.offset));
+    }
+    let mut payload = vec![AUTH_MAGIC];
+    payload.extend(encode_list(&fields[..3], bytes, &[]));
+    let authority = to_parity(to_u256(&fields[3])?)
+        .and_then(|parity| {
+            recover(
+                keccak256(payload),
+                parity,
+                to_u256(&fields[4])?,
+                to_u256(&fields[5])?,
+            )
+        })
+        .map(|a| json!(a.to_checksum(None)))
+        .unwrap_or(Value::Null);
+    Ok(json!({
+        "chainId": quantity(&fields[0])?,
+        "address": to_address(&fields[1])?.to_checksum(None),
+        "nonce": quantity(&fields[2])?,
+        "yParity": quantity(&fields[3])?,
+        "r": format!("{:#x}", to_u256(&fields[4])?),
+        "s": format!("{:#x}", to_u256(&fields[5])?),
+        "authority": authority,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The signed transaction of the EIP-155 example (nonce 9, 1 ether to 0x3535...).
+    const EIP155_TX: &str = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+    fn key() -> k256::ecdsa::SigningKey {
+        signing_key(&ParseResult::Value(U256::from(1))).unwrap()
+    }
+
+    fn list(payload: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        encode_list_payload(payload, &mut out);
+        out
+    }
+
+    fn dead() -> Address {
+        "0x000000000000000000000000000000000000dEaD"
+            .parse()
+            .unwrap()
+    }
+
+    /// Fields shared by the typed transactions: chain 1, nonce 0, fees 1 and 2, 21000 gas,
+    /// 1 wei to 0xdead, with `0xabcd` as input.
+    fn typed_fields(tx_type: u8) -> Vec<u8> {
+        let mut fields = Vec::new();
+        for n in [1u64, 0, 1] {
+            encode_uint(U256::from(n), &mut fields);
+        }
+        if tx_type != 1 {
+            encode_uint(U256::from(2), &mut fields);
+        }
+        encode_uint(U256::from(21000), &mut fields);
+        dead().as_slice().encode(&mut fields);
+        encode_uint(U256::from(1), &mut fields);
+        [0xab, 0xcd].as_slice().encode(&mut fields);
+        // access list: [[0xdead, [0x00..01]]]
+        let mut entry = Vec::new();
+        dead().as_slice().encode(&mut entry);
+        entry.extend(list(&alloy_rlp::encode(B256::with_last_byte(1).as_slice())));
+        fields.extend(list(&list(&entry)));
+        fields
+    }
+
+    /// Signs the unsigned fields of a typed transaction with the key 1, as a raw transaction.
+    fn sign_typed(tx_type: u8, mut fields: Vec<u8>) -> String {
+        let mut unsigned = vec![tx_type];
+        unsigned.extend(list(&fields));
+        let sig = sign(keccak256(&unsigned), &key()).unwrap();
+        encode_uint(U256::from(sig.y_parity as u8), &mut fields);
+        encode_uint(sig.r, &mut fields);
+        encode_uint(sig.s, &mut fields);
+        let mut signed = vec![tx_type];
+        signed.extend(list(&fields));
+        format!("0x{}", hex::encode(signed))
+    }
+
+    #[test]
+    fn decode_eip155_example() {
+        let tx = decode_tx(EIP155_TX, None).unwrap();
+        assert_eq!(tx["type"], 0);
+        assert_eq!(tx["chainId"], 1);
+        assert_eq!(tx["nonce"], 9);
+        assert_eq!(tx["value"], 1_000_000_000_000_000_000u64);
+        assert_eq!(
+            tx["hash"],
+            "0x33469b22e9f636356c4160a87eb19df52b7412e8eac32a4a55ffe88ea8350788"
+        );
+        assert_eq!(tx["from"], "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+    }
+
+    #[test]
+    fn decode_typed_envelopes() {
+        let signer = Address::from_private_key(&key()).to_checksum(None);
+
+        let mut eip4844 = typed_fields(3);
+        encode_uint(U256::from(3), &mut eip4844);
+        eip4844.extend(list(&alloy_rlp::encode(B256::with_last_byte(1).as_slice())));
+
+        let mut eip7702 = typed_fields(4);
+        let auth = sign(auth_hash(U256::from(1), dead(), U256::ZERO), &key()).unwrap();
+        let mut tuple = Vec::new();
+        encode_uint(U256::from(1), &mut tuple);
+        dead().as_slice().encode(&mut tuple);
+        encode_uint(U256::ZERO, &mut tuple);
+        encode_uint(U256::from(auth.y_parity as u8), &mut tuple);
+        encode_uint(auth.r, &mut tuple);
+        encode_uint(auth.s, &mut tuple);
+        eip7702.extend(list(&list(&tuple)));
+
+        for (tx_type, fields) in [
+            (1, typed_fields(1)),
+            (2, typed_fields(2)),
+            (3, eip4844),
+            (4, eip7702),
+        ] {
+            let raw = sign_typed(tx_type, fields);
+            let tx = decode_tx(&raw, None).unwrap();
+            assert_eq!(tx["type"], tx_type);
+            assert_eq!(tx["from"], signer);
+            assert_eq!(
+                tx["hash"],
+                keccak256(hex::decode(&raw).unwrap()).to_string()
+            );
+            assert_eq!(tx["input"], "0xabcd");
+            assert_eq!(tx["accessList"][0]["address"], dead().to_checksum(None));
+            match tx_type {
+                1 => assert_eq!(tx["gasPrice"], 1),
+                2 => assert_eq!(tx["maxFeePerGas"], 2),
+                3 => assert_eq!(
+                    tx["blobVersionedHashes"][0],
+                    B256::with_last_byte(1).to_string()
+                ),
+                _ => assert_eq!(tx["authorizationList"][0]["authority"], signer),
+            }
+        }
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert!(decode_tx("0x", None).is_err());
+        assert!(decode_tx("0x02", None).is_err());
+        assert_eq!(
+            decode_tx("0xc0", None).unwrap_err(),
+            "expected 9 fields, found 0"
+        );
+        assert_eq!(
+            decode_tx(&format!("{}00", EIP155_TX), None).unwrap_err(),
+            "trailing bytes after offset 110"
+        );
+        assert!(decode_tx("0x05c0", None).is_err());
+    }
+
+    #[test]
+    fn decode_input_errors_keep_the_transaction() {
+        let tx = decode_tx(EIP155_TX, Some("deposit()")).unwrap();
+        assert_eq!(tx["from"], "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+        assert!(tx["decodedInput"]["error"].is_string());
+
+        let raw = sign_typed(2, typed_fields(2));
+        let tx = decode_tx(&raw, Some("f(bytes2)")).unwrap();
+        assert!(tx["decodedInput"]["error"].is_string());
+    }
+
+    #[test]
+    fn auth_hash_of_hand_encoded_tuple() {
+        let address: Address = "0x000000000000000000000000000000000000dEaD"
+            .parse()
+            .unwrap();
+        // 0x05 || rlp([1, address, 0]): a 23-byte list of 0x01, a 20-byte string and 0x80
+        let mut payload = hex::decode("05d70194").unwrap();
+        payload.extend_from_slice(address.as_slice());
+        payload.push(0x80);
+        assert_eq!(
+            auth_hash(U256::from(1), address, U256::ZERO),
+            keccak256(payload)
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use crate::parser::types::abi::abi_encode;
+
+use alloy_core::primitives::{hex, keccak256, Address, B256, U256};
+use serde_json::{json, Map, Value};
+
+/// Computes the ERC-4337 `userOpHash` of a user operation, as returned by
+/// `EntryPoint.getUserOpHash`. Supports the v0.6 `UserOperation` layout, the v0.7
+/// `PackedUserOperation` layout and the unpacked v0.7 RPC format.
+///
+/// Operations with `initCode` or `paymasterAndData` but no `accountGasLimits` are v0.6 ones,
+/// and mixing them with the unpacked v0.7 fields is an error rather than dropping fields.
+pub fn user_op_hash(op: &Value, entry_point: Address, chain_id: U256) -> Result<Value, String> {
+    let op = op
+        .as_object()
+        .ok_or("user operation must be a json object")?;
+    let packed_fields = ["initCode", "paymasterAndData"];
+    let unpacked_fields = [
+        "factory",
+        "factoryData",
+        "paymaster",
+        "paymasterVerificationGasLimit",
+        "paymasterPostOpGasLimit",
+        "paymasterData",
+    ];
+    let is_v06 = !op.contains_key("accountGasLimits")
+        && packed_fields.iter().any(|name| op.contains_key(*name));
+    if is_v06 && unpacked_fields.iter().any(|name| op.contains_key(*name)) {
+        return Err(format!(
+            "ambiguous user operation: v0.6 fields ({}) mixed with v0.7 ones ({})",
+            packed_fields.join(", "),
+            unpacked_fields.join(", ")
+        ));
+    }
+    let mut result = Map::new();
+    let (abi, args) = if is_v06 {
+        result.insert("version".to_string(), json!("0.6"));
+        (
+            "(address,uint256,bytes32,bytes32,uint256,uint256,uint256,uint256,uint256,bytes32)",
+            vec![
+                address(op, "sender")?.to_checksum(None),
+                uint(op, "nonce")?.to_string(),
+                keccak256(bytes(op, "initCode")?).to_string(),
+                keccak256(bytes(op, "callData")?).to_string(),
+                uint(op, "callGasLimit")?.to_string(),
+                uint(op, "verificationGasLimit")?.to_string(),
+                uint(op, "preVerificationGas")?.to_string(),
+                uint(op, "maxFeePerGas")?.to_string(),
+                uint(op, "maxPriorityFeePerGas")?.to_string(),
+                keccak256(bytes(op, "paymasterAndData")?).to_string(),
+            ],
+        )
+    } else {
+        result.insert("version".to_string(), json!("0.7"));
+        let packed = pack_user_op(op)?;
+        let args = vec![
+            address(op, "sender")?.to_checksum(None),
+            uint(op, "nonce")?.to_string(),
+            keccak256(&packed.init_code).to_string(),
+            keccak256(bytes(op, "callData")?).to_string(),
+            packed.account_gas_limits.to_string(),
+            uint(op, "preVerificationGas")?.to_string(),
+            packed.gas_fees.to_string(),
+            keccak256(&packed.paymaster_and_data).to_string(),
+        ];
+        result.insert(
+            "initCode".to_string(),
+            json!(format!("0x{}", hex::encode(&packed.init_code))),
+        );
+        result.insert(
+            "accountGasLimits".to_string(),
+            json!(packed.account_gas_limits.to_string()),
+        );
+        result.insert("gasFees".to_string(), json!(packed.gas_fees.to_string()));
+        result.insert(
+            "paymasterAndData".to_string(),
+            json!(format!("0x{}", hex::encode(&packed.paymaster_and_data))),
+        );
+        (
+            "(address,uint256,bytes32,bytes32,bytes32,uint256,bytes32,bytes32)",
+            args,
+        )
+    };
+
+    let encoded = abi_encode(abi, args, false)?;
+    let hash = keccak256(hex::decode(&encoded).map_err(|e| e.to_string())?);
+    let user_op_hash = abi_encode(
+        "(bytes32,address,uint256)",
+        vec![
+            hash.to_string(),
+            entry_point.to_checksum(None),
+            chain_id.to_string(),
+        ],
+        false,
+    )?;
+    result.insert("encoded".to_string(), json!(encoded));
+    result.insert(
+        "userOpHash".to_string(),
+        json!(keccak256(hex::decode(user_op_hash).map_err(|e| e.to_string())?).to_string()),
+    );
+    Ok(Value::Object(result))
+}
+
+struct PackedFields {
+    init_code: Vec<u8>,
+    account_gas_limits: B256,
+    gas_fees: B256,
+    paymaster_and_data: Vec<u8>,
+}
+
+/// Reads the packed v0.7 fields, packing them first when the operation uses the RPC format
+/// (`factory`, `callGasLimit`, `paymaster`...).
+fn pack_user_op(op: &Map<String, Value>) -> Result<PackedFields, String> {
+    if op.contains_key("accountGasLimits") {
+        return Ok(PackedFields {
+            init_code: bytes(op, "initCode")?,
+            account_gas_limits: word(op, "accountGasLimits")?,
+            gas_fees: word(op, "gasFees")?,
+            paymaster_and_data: bytes(op, "paymasterAndData")?,
+        });
+    }
+    let mut init_code = Vec::new();
+    if has_value(op, "factory") {
+        init_code.extend_from_slice(address(op, "factory")?.as_slice());
+        init_code.extend(bytes(op, "factoryData")?);
+    }
+    let mut paymaster_and_data = Vec::new();
+    if has_value(op, "paymaster") {
+        paymaster_and_data.extend_from_slice(address(op, "paymaster")?.as_slice());
+        let gas_limits = pack_uint128(
+            uint(op, "paymasterVerificationGasLimit")?,
+            uint(op, "paymasterPostOpGasLimit")?,
+        )?;
+        paymaster_and_data.extend_from_slice(gas_limits.as_slice());
+        paymaster_and_data.extend(bytes(op, "paymasterData")?);
+    }
+    Ok(PackedFields {
+        init_code,
+        account_gas_limits: pack_uint128(
+            uint(op, "verificationGasLimit")?,
+            uint(op, "callGasLimit")?,
+        )?,
+        gas_fees: pack_uint128(uint(op, "maxPriorityFeePerGas")?, uint(op, "maxFeePerGas")?)?,
+        paymaster_and_data,
+    })
+}
+
+/// Packs two uint128 values into a single word: `high << 128 | low`. Used by the v0.7
+/// `accountGasLimits` (verification, call) and `gasFees` (priority fee, max fee) fields.
+pub fn pack_uint128(high: U256, low: U256) -> Result<B256, String> {
+    if high.bit_len() > 128 || low.bit_len() > 128 {
+        return Err("values must fit in 128 bits".to_string());
+    }
+    Ok(B256::from((high << 128) | low))
+}
+
+/// Splits a word into its high and low uint128 halves.
+pub fn unpack_uint128(word: U256) -> (U256, U256) {
+    (word >> 128, word & (U256::MAX >> 128))
+}
+
+fn has_value(op: &Map<String, Value>, name: &str) -> bool {
+    match op.get(name) {
+        None | Some(Value::Null) => false,
+        Some(Value::String(s)) => !s.is_empty() && s != "0x",
+        Some(_) => true,
+    }
+}
+
+fn uint(op: &Map<String, Value>, name: &str) -> Result<U256, String> {
+    match op.get(name) {
+        Some(Value::String(s)) => s.parse::<U256>().map_err(|e| format!("{}: {}", name, e)),
+        Some(Value::Number(n)) => n
+            .to_string()
+            .parse::<U256>()
+            .map_err(|e| format!("{}: {}", name, e)),
+        None | Some(Value::Null) => Err(format!("missing field: {}", name)),
+        Some(_) => Err(format!("{}: expected a number", name)),
+    }
+}
+
+fn bytes(op: &Map<String, Value>, name: &str) -> Result<Vec<u8>, String> {
+    match op.get(name) {
+        Some(Value::String(s)) => hex::decode(s).map_err(|e| format!("{}: {}", name, e)),
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(_) => Err(format!("{}: expected hex bytes", name)),
+    }
+}
+
+fn word(op: &Map<String, Value>, name: &str) -> Result<B256, String> {
+    let bytes = bytes(op, name)?;
+    if bytes.len() != 32 {
+        return Err(format!("{}: expected 32 bytes", name));
+    }
+    Ok(B256::from_slice(&bytes))
+}
+
+fn address(op: &Map<String, Value>, name: &str) -> Result<Address, String> {
+    match op.get(name) {
+        Some(Value::String(s)) => s.parse::<Address>().map_err(|e| format!("{}: {}", name, e)),
+        _ => Err(format!("{}: expected an address", name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRY_POINT_V06: &str = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789";
+    const ENTRY_POINT_V07: &str = "0x0000000071727De22E5E9d8BAf0edAc6f37da032";
+
+    fn word(value: U256) -> [u8; 32] {
+        value.to_be_bytes()
+    }
+
+    /// `keccak256(abi.encode(keccak256(packed), entryPoint, chainId))`, encoded word by word.
+    fn expected_hash(packed: &[[u8; 32]], entry_point: &str, chain_id: u64) -> String {
+        let inner = keccak256(packed.concat());
+        let entry_point: Address = entry_point.parse().unwrap();
+        keccak256(
+            [
+                inner.0,
+                entry_point.into_word().0,
+                word(U256::from(chain_id)),
+            ]
+            .concat(),
+        )
+        .to_string()
+    }
+
+    #[test]
+    fn v06_user_op_hash() {
+        let sender: Address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
+        let op = json!({
+            "sender": sender.to_checksum(None),
+            "nonce": "0x1",
+            "initCode": "0x",
+            "callData": "0xb61d27f6",
+            "callGasLimit": "0x5208",
+            "verificationGasLimit": "100000",
+            "preVerificationGas": 50000,
+            "maxFeePerGas": "0x3b9aca00",
+            "maxPriorityFeePerGas": "0x3b9aca00",
+            "paymasterAndData": "0x",
+            "signature": "0x",
+        });
+        let packed = [
+            sender.into_word().0,
+            word(U256::from(1)),
+            keccak256([]).0,
+            keccak256(hex::decode("b61d27f6").unwrap()).0,
+            word(U256::from(21000)),
+            word(U256::from(100000)),
+            word(U256::from(50000)),
+            word(U256::from(1_000_000_000)),
+            word(U256::from(1_000_000_000)),
+            keccak256([]).0,
+        ];
+        let hash = user_op_hash(&op, ENTRY_POINT_V06.parse().unwrap(), U256::from(1)).unwrap();
+        assert_eq!(hash["version"], "0.6");
+        assert_eq!(
+            hash["userOpHash"],
+            expected_hash(&packed, ENTRY_POINT_V06, 1)
+        );
+    }
+
+    #[test]
+    fn v07_user_op_hash_packed_and_unpacked() {
+        let sender: Address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
+        let factory = "0x9406Cc6185a346906296840746125a0E44976454";
+        let paymaster = "0x0000000000000000000000000000000000000001";
+        let unpacked = json!({
+            "sender": sender.to_checksum(None),
+            "nonce": "0x0",
+            "factory": factory,
+            "factoryData": "0x1234",
+            "callData": "0x",
+            "callGasLimit": 2,
+            "verificationGasLimit": 1,
+            "preVerificationGas": 3,
+            "maxFeePerGas": 5,
+            "maxPriorityFeePerGas": 4,
+            "paymaster": paymaster,
+            "paymasterVerificationGasLimit": 6,
+            "paymasterPostOpGasLimit": 7,
+            "paymasterData": "0xff",
+        });
+        let init_code = format!("{}1234", factory.to_lowercase());
+        let paymaster_and_data = format!("{}{:032x}{:032x}ff", paymaster.to_lowercase(), 6, 7);
+        let account_gas_limits = format!("0x{:032x}{:032x}", 1, 2);
+        let gas_fees = format!("0x{:032x}{:032x}", 4, 5);
+        let packed = json!({
+            "sender": sender.to_checksum(None),
+            "nonce": "0x0",
+            "initCode": init_code,
+            "callData": "0x",
+            "accountGasLimits": account_gas_limits,
+            "preVerificationGas": 3,
+            "gasFees": gas_fees,
+            "paymasterAndData": paymaster_and_data,
+        });
+        let words = [
+            sender.into_word().0,
+            word(U256::ZERO),
+            keccak256(hex::decode(&init_code).unwrap()).0,
+            keccak256([]).0,
+            word(U256::from(1) << 128 | U256::from(2)),
+            word(U256::from(3)),
+            word(U256::from(4) << 128 | U256::from(5)),
+            keccak256(hex::decode(&paymaster_and_data).unwrap()).0,
+        ];
+        let expected = expected_hash(&words, ENTRY_POINT_V07, 11155111);
+        for op in [unpacked, packed] {
+            let hash =
+                user_op_hash(&op, ENTRY_POINT_V07.parse().unwrap(), U256::from(11155111)).unwrap();
+            assert_eq!(hash["version"], "0.7");
+            assert_eq!(hash["userOpHash"], expected);
+        }
+    }
+
+    #[test]
+    fn v06_user_op_without_init_code() {
+        let sender: Address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
+        let mut op = json!({
+            "sender": sender.to_checksum(None),
+            "nonce": 0,
+            "callData": "0x",
+            "callGasLimit": 1,
+            "verificationGasLimit": 2,
+            "preVerificationGas": 3,
+            "maxFeePerGas": 4,
+            "maxPriorityFeePerGas": 5,
+            "paymasterAndData": "0x1234",
+        });
+        let packed = [
+            sender.into_word().0,
+            word(U256::ZERO),
+            keccak256([]).0,
+            keccak256([]).0,
+            word(U256::from(1)),
+            word(U256::from(2)),
+            word(U256::from(3)),
+            word(U256::from(4)),
+            word(U256::from(5)),
+            keccak256(hex::decode("1234").unwrap()).0,
+        ];
+        let hash = user_op_hash(&op, ENTRY_POINT_V06.parse().unwrap(), U256::from(1)).unwrap();
+        assert_eq!(hash["version"], "0.6");
+        assert_eq!(
+            hash["userOpHash"],
+            expected_hash(&packed, ENTRY_POINT_V06, 1)
+        );
+
+        op["paymaster"] = json!("0x0000000000000000000000000000000000000001");
+        assert!(user_op_hash(&op, ENTRY_POINT_V06.parse().unwrap(), U256::from(1)).is_err());
+    }
+}